@@ -14,9 +14,4 @@ impl Bot {
             data: Arc::new(RwLock::new(data)),
         }
     }
-
-    pub async fn save_data(&self) -> anyhow::Result<()> {
-        let data = self.data.read().await;
-        data.save().await
-    }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serenity::async_trait;
+use std::sync::Arc;
+
+/// Where the bot gets the current time. Everything that decides streaks, deadlines or
+/// post times asks `BotData::now` instead of calling `Utc::now()`, and the scheduler
+/// waits with `sleep_until`, so tests can run it against a fake clock.
+#[async_trait]
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait until this clock reads `deadline` or later
+    async fn sleep_until(&self, deadline: DateTime<Utc>);
+}
+
+/// The real wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        if let Ok(duration) = (deadline - Utc::now()).to_std() {
+            tokio::time::sleep(duration).await;
+        }
+    }
+}
+
+/// The clock `BotData` starts with
+pub fn system() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+/// A clock that only moves when told to
+#[cfg(test)]
+#[derive(Debug)]
+pub struct FakeClock {
+    now: std::sync::Mutex<DateTime<Utc>>,
+    moved: tokio::sync::Notify, // wakes sleepers to look at the new time
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> Arc<Self> {
+        Arc::new(Self { now: std::sync::Mutex::new(now), moved: tokio::sync::Notify::new() })
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+        self.moved.notify_waiters();
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.now.lock().unwrap() += by;
+        self.moved.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        loop {
+            // Registered before reading the time, so a move in between isn't missed
+            let moved = self.moved.notified();
+            if self.now() >= deadline {
+                return;
+            }
+            moved.await;
+        }
+    }
+}
@@ -1,22 +1,24 @@
 use serenity::{
-    builder::{CreateCommand, CreateCommandOption},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
     model::application::{CommandInteraction, CommandOptionType},
     prelude::*,
 };
 use crate::{
     bot::SharedBotData,
-    data::ServerConfig,
+    data::{CheckinFeedback, CheckinRequirements, ServerConfig, StreakPolicy},
+    permissions::Access,
+    schedule::PostSchedule,
     utils::{
-        command_helpers::{get_guild_id, get_channel_option, get_string_option, is_admin, validate_timezone, validate_time_format},
-        responses::{default_response},
+        command_helpers::{get_guild_id, get_channel_option, get_string_option, get_integer_option, get_boolean_option, get_role_option, access, validate_timezone, validate_time_format, validate_cron_expression, validate_emoji},
+        responses::{default_response, embed_response},
     },
 };
-use chrono::Utc;
+use chrono_tz::Tz;
 use tracing::{info, debug, error};
 
 pub fn set_channel_command() -> CreateCommand {
     CreateCommand::new("set-checkin-channel")
-        .description("Configure the daily check-in channel (Admin only)")
+        .description("Configure the daily check-in channel (Admins and managers)")
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::Channel,
@@ -34,9 +36,9 @@ pub async fn set_channel(
 ) -> serenity::Result<()> {
     info!("Set checkin channel command executed by user {}", command.user.id);
     
-    // Check admin permissions
-    if !is_admin(ctx, command).await? {
-        let response = default_response("This command requires administrator permissions.");
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
         command.create_response(&ctx.http, response).await?;
         return Ok(());
     }
@@ -53,18 +55,11 @@ pub async fn set_channel(
         let mut server_config = bot_data
             .get_server_config(&guild_id)
             .cloned()
-            .unwrap_or_else(|| ServerConfig {
-                guild_id: guild_id.clone(),
-                checkin_channel_id: None,
-                timezone: "UTC".to_string(), // Default timezone
-                daily_time: "09:00".to_string(), // Default time
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-            });
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
         
         // Update the channel ID and timestamp
         server_config.checkin_channel_id = Some(channel_id.to_string());
-        server_config.updated_at = Utc::now();
+        server_config.updated_at = bot_data.now();
         
         // Save to data store
         bot_data.add_or_update_server(server_config);
@@ -87,7 +82,7 @@ pub async fn set_channel(
 
 pub fn set_checkin_time_command() -> CreateCommand {
     CreateCommand::new("set-checkin-time")
-        .description("Configure the daily check-in time and timezone (Admin only)")
+        .description("Configure the daily check-in time and timezone (Admins and managers)")
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::String,
@@ -113,9 +108,9 @@ pub async fn set_checkin_time(
 ) -> serenity::Result<()> {
     info!("Set checkin time command executed by user {}", command.user.id);
     
-    // Check admin permissions
-    if !is_admin(ctx, command).await? {
-        let response = default_response("This command requires administrator permissions.");
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
         command.create_response(&ctx.http, response).await?;
         return Ok(());
     }
@@ -159,21 +154,15 @@ pub async fn set_checkin_time(
         let mut server_config = bot_data
             .get_server_config(&guild_id)
             .cloned()
-            .unwrap_or_else(|| ServerConfig {
-                guild_id: guild_id.clone(),
-                checkin_channel_id: None,
-                timezone: "UTC".to_string(),
-                daily_time: "09:00".to_string(),
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-            });
-        
-        // Update the time and timezone
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Update the time and timezone; a fixed daily time replaces any cron schedule
         server_config.daily_time = validated_time.clone();
+        server_config.cron_schedule = None;
         if command.data.options.iter().any(|opt| opt.name == "timezone") {
             server_config.timezone = validated_timezone.clone();
         }
-        server_config.updated_at = Utc::now();
+        server_config.updated_at = bot_data.now();
         
         // Save to data store
         bot_data.add_or_update_server(server_config);
@@ -197,3 +186,665 @@ pub async fn set_checkin_time(
     command.create_response(&ctx.http, response).await?;
     Ok(())
 }
+
+pub fn set_checkin_schedule_command() -> CreateCommand {
+    CreateCommand::new("set-checkin-schedule")
+        .description("Configure a cron schedule for check-in posts (Admins and managers)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "schedule",
+                "Cron expression: minute hour day-of-month month day-of-week (e.g., 0 9 * * Mon-Fri)"
+            )
+            .required(true)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "timezone",
+                "Timezone (e.g., America/New_York, Europe/London, UTC)"
+            )
+            .required(false)
+        )
+}
+
+pub async fn set_checkin_schedule(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    info!("Set checkin schedule command executed by user {}", command.user.id);
+    
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    
+    // Get guild ID
+    let guild_id = get_guild_id(command)?;
+    
+    // Get and validate the cron expression
+    let schedule_str = get_string_option(command, "schedule")?;
+    let validated_schedule = match validate_cron_expression(&schedule_str) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Invalid cron expression: {}", e);
+            let response = default_response("Invalid cron expression. Use five fields: minute hour day-of-month month day-of-week (e.g., `0 9 * * Mon-Fri` for weekdays at 09:00, `0 9,18 * * *` for twice a day).");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+    
+    // Get and validate timezone (optional)
+    let timezone_option = match get_string_option(command, "timezone") {
+        Ok(timezone_str) => match validate_timezone(&timezone_str) {
+            Ok(tz) => Some(tz),
+            Err(e) => {
+                error!("Invalid timezone: {}", e);
+                let response = default_response("Invalid timezone. Use format like 'America/New_York', 'Europe/London', or 'UTC'.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => None,
+    };
+    
+    // Update server configuration
+    let timezone = {
+        let mut bot_data = data.write().await;
+        
+        // Get existing server config or create new one
+        let mut server_config = bot_data
+            .get_server_config(&guild_id)
+            .cloned()
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Update the schedule and timezone
+        server_config.cron_schedule = Some(validated_schedule.clone());
+        if let Some(tz) = &timezone_option {
+            server_config.timezone = tz.clone();
+        }
+        server_config.updated_at = bot_data.now();
+        let timezone = server_config.timezone.clone();
+        
+        // Save to data store
+        bot_data.add_or_update_server(server_config);
+        
+        // Persist to disk
+        if let Err(e) = bot_data.save().await {
+            error!("Failed to save data after setting checkin schedule: {}", e);
+            let response = default_response("Failed to save configuration. Please try again.");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+        
+        timezone
+    };
+    
+    debug!("Successfully configured checkin schedule '{}' {} for guild {}", validated_schedule, timezone, guild_id);
+    
+    // Preview the next five posts
+    let preview = match (PostSchedule::parse_cron(&validated_schedule), timezone.parse::<Tz>()) {
+        (Ok(schedule), Ok(tz)) => schedule
+            .upcoming(data.read().await.now(), tz, 5)
+            .iter()
+            .map(|fire_time| format!("• <t:{}:F>", fire_time.timestamp()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+    
+    let response = default_response(&format!(
+        "Daily check-in schedule has been set to `{}` ({} timezone)!\n\n**Next posts:**\n{}",
+        validated_schedule, timezone, preview
+    ));
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+pub fn streak_policy_command() -> CreateCommand {
+    CreateCommand::new("streak-policy")
+        .description("View or change the server's grace period rules (Admins and managers)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "min-streak",
+                "Streak length needed before missed days can be excused"
+            )
+            .min_int_value(0)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "max-consecutive-misses",
+                "Most check-in days in a row the grace period covers (0 disables it)"
+            )
+            .min_int_value(0)
+            .max_int_value(30)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "max-grace-days",
+                "Most grace days a member can use within the window"
+            )
+            .min_int_value(0)
+            .max_int_value(365)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "window-days",
+                "Length of the rolling window grace days are counted over"
+            )
+            .min_int_value(1)
+            .max_int_value(365)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "grace-counts",
+                "Whether excused days are added to the streak"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "freeze-every",
+                "Streak days between freeze token rewards (0 disables freezes)"
+            )
+            .min_int_value(0)
+            .max_int_value(365)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "max-freezes",
+                "Most freeze tokens a member can hold"
+            )
+            .min_int_value(0)
+            .max_int_value(10)
+            .required(false)
+        )
+}
+
+pub async fn streak_policy(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    info!("Streak policy command executed by user {}", command.user.id);
+    
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    
+    // Get guild ID
+    let guild_id = get_guild_id(command)?;
+    
+    let (policy, changed) = {
+        let mut bot_data = data.write().await;
+        
+        // Get existing server config or create new one
+        let mut server_config = bot_data
+            .get_server_config(&guild_id)
+            .cloned()
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Apply whichever options were given; none at all just shows the policy
+        let policy = &mut server_config.streak_policy;
+        if let Ok(value) = get_integer_option(command, "min-streak") {
+            policy.grace_min_streak = value.max(0) as u32;
+        }
+        if let Ok(value) = get_integer_option(command, "max-consecutive-misses") {
+            policy.grace_max_consecutive_misses = value.max(0) as u32;
+        }
+        if let Ok(value) = get_integer_option(command, "max-grace-days") {
+            policy.grace_max_days_per_window = value.max(0) as u32;
+        }
+        if let Ok(value) = get_integer_option(command, "window-days") {
+            policy.grace_window_days = value.max(1) as u32;
+        }
+        if let Ok(value) = get_boolean_option(command, "grace-counts") {
+            policy.grace_days_count_toward_streak = value;
+        }
+        if let Ok(value) = get_integer_option(command, "freeze-every") {
+            policy.freeze_every_days = value.max(0) as u32;
+        }
+        if let Ok(value) = get_integer_option(command, "max-freezes") {
+            policy.freeze_max_tokens = value.max(0) as u32;
+        }
+        
+        let policy = server_config.streak_policy.clone();
+        let changed = bot_data
+            .get_server_config(&guild_id)
+            .map(|existing| existing.streak_policy != policy)
+            .unwrap_or(policy != StreakPolicy::default());
+        
+        if changed {
+            server_config.updated_at = bot_data.now();
+            bot_data.add_or_update_server(server_config);
+            
+            // Persist to disk
+            if let Err(e) = bot_data.save().await {
+                error!("Failed to save data after updating streak policy: {}", e);
+                let response = default_response("Failed to save configuration. Please try again.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+        
+        (policy, changed)
+    };
+    
+    if changed {
+        debug!("Updated streak policy for guild {}: {:?}", guild_id, policy);
+    }
+    
+    let grace = if policy.grace_max_consecutive_misses == 0 || policy.grace_max_days_per_window == 0 {
+        "Disabled - any missed check-in day resets a streak".to_string()
+    } else {
+        format!(
+            "Streaks of **{}+** days can miss up to **{}** check-in day(s) in a row, \
+             and up to **{}** day(s) in any **{}**-day window",
+            policy.grace_min_streak,
+            policy.grace_max_consecutive_misses,
+            policy.grace_max_days_per_window,
+            policy.grace_window_days
+        )
+    };
+    let counts = if policy.grace_days_count_toward_streak {
+        "Yes - excused days are added to the streak"
+    } else {
+        "No - the streak continues but excused days are not added"
+    };
+    
+    let freezes = if policy.freeze_every_days == 0 || policy.freeze_max_tokens == 0 {
+        "Disabled".to_string()
+    } else {
+        format!(
+            "One token every **{}** streak days, holding up to **{}**; used after the grace period",
+            policy.freeze_every_days, policy.freeze_max_tokens
+        )
+    };
+    
+    let embed = CreateEmbed::new()
+        .title(if changed { "✅ Streak Policy Updated" } else { "📜 Streak Policy" })
+        .field("🛟 Grace Period", grace, false)
+        .field("➕ Grace Days Count Toward Streak", counts, false)
+        .field("🧊 Streak Freezes", freezes, false)
+        .color(0x5865F2);
+    
+    command.create_response(&ctx.http, embed_response(embed)).await?;
+    Ok(())
+}
+
+/// Longest check-in keyword admins can require
+const MAX_KEYWORD_LENGTH: u16 = 50;
+
+pub fn checkin_requirements_command() -> CreateCommand {
+    CreateCommand::new("checkin-requirements")
+        .description("View or change what a check-in must contain to count (Admins and managers)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "min-length",
+                "Fewest characters a check-in needs (0 accepts any length)"
+            )
+            .min_int_value(0)
+            .max_int_value(2000)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "require-attachment",
+                "Whether a check-in needs an attachment, like a photo as proof"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "keyword",
+                "A word or hashtag a check-in must mention, or 'none'"
+            )
+            .max_length(MAX_KEYWORD_LENGTH)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "keep-deleted",
+                "Whether a check-in still counts after its message is deleted (default: no)"
+            )
+            .required(false)
+        )
+}
+
+pub async fn checkin_requirements(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    info!("Checkin requirements command executed by user {}", command.user.id);
+    
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    
+    // Get guild ID
+    let guild_id = get_guild_id(command)?;
+    
+    let (requirements, keep_deleted, changed) = {
+        let mut bot_data = data.write().await;
+        
+        // Get existing server config or create new one
+        let mut server_config = bot_data
+            .get_server_config(&guild_id)
+            .cloned()
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Apply whichever options were given; none at all just shows the requirements
+        let requirements = &mut server_config.checkin_requirements;
+        if let Ok(value) = get_integer_option(command, "min-length") {
+            requirements.min_length = value.max(0) as u32;
+        }
+        if let Ok(value) = get_boolean_option(command, "require-attachment") {
+            requirements.require_attachment = value;
+        }
+        if let Ok(value) = get_string_option(command, "keyword") {
+            requirements.keyword = if value.eq_ignore_ascii_case("none") { None } else { Some(value) };
+        }
+        if let Ok(value) = get_boolean_option(command, "keep-deleted") {
+            server_config.keep_deleted_checkins = value;
+        }
+        
+        let requirements = server_config.checkin_requirements.clone();
+        let keep_deleted = server_config.keep_deleted_checkins;
+        let changed = bot_data
+            .get_server_config(&guild_id)
+            .map(|existing| existing.checkin_requirements != requirements || existing.keep_deleted_checkins != keep_deleted)
+            .unwrap_or(requirements != CheckinRequirements::default() || keep_deleted);
+        
+        if changed {
+            server_config.updated_at = bot_data.now();
+            bot_data.add_or_update_server(server_config);
+            
+            // Persist to disk
+            if let Err(e) = bot_data.save().await {
+                error!("Failed to save data after updating check-in requirements: {}", e);
+                let response = default_response("Failed to save configuration. Please try again.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+        
+        (requirements, keep_deleted, changed)
+    };
+    
+    if changed {
+        debug!("Updated check-in requirements for guild {}: {:?}, keep deleted: {}", guild_id, requirements, keep_deleted);
+    }
+    
+    let mut embed = CreateEmbed::new()
+        .title(if changed { "✅ Check-in Requirements Updated" } else { "📝 Check-in Requirements" })
+        .color(0x5865F2);
+    if requirements.is_empty() {
+        embed = embed.description("None - any reply in the check-in thread counts");
+    } else {
+        let min_length = match requirements.min_length {
+            0 => "Any".to_string(),
+            length => format!("At least **{}** characters", length),
+        };
+        embed = embed
+            .field("✏️ Length", min_length, false)
+            .field("📎 Attachment", if requirements.require_attachment { "Required" } else { "Optional" }, false)
+            .field("🏷️ Keyword", requirements.keyword.as_deref().map(|keyword| format!("`{}`", keyword)).unwrap_or_else(|| "None".to_string()), false)
+            .footer(CreateEmbedFooter::new("Replies that fall short get a ❌ reaction and an explanation"));
+    }
+    embed = embed.field(
+        "🗑️ Deleted Check-ins",
+        if keep_deleted { "Still count" } else { "Are revoked" },
+        false,
+    );
+    
+    command.create_response(&ctx.http, embed_response(embed)).await?;
+    Ok(())
+}
+
+pub fn checkin_feedback_command() -> CreateCommand {
+    CreateCommand::new("checkin-feedback")
+        .description("View or change how the bot acknowledges check-ins in the thread (Admins and managers)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "emoji",
+                "Reaction on replies that count, e.g. ✅ or a server emoji, or 'none'"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "milestone-emoji",
+                "Reaction instead when a streak reaches a milestone, or 'none'"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "reply-with-streak",
+                "Whether to also reply with the member's new streak"
+            )
+            .required(false)
+        )
+}
+
+pub async fn checkin_feedback(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    info!("Checkin feedback command executed by user {}", command.user.id);
+    
+    // Check admin or manager permissions
+    if access(ctx, command, &data).await? < Access::Manager {
+        let response = default_response("This command is for administrators and members with a manager role.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    
+    // Get guild ID
+    let guild_id = get_guild_id(command)?;
+    
+    // Validate the emoji before touching the configuration
+    let mut emojis = [None, None];
+    for (emoji, name) in emojis.iter_mut().zip(["emoji", "milestone-emoji"]) {
+        let Ok(value) = get_string_option(command, name) else {
+            continue;
+        };
+        match validate_emoji(&value) {
+            Ok(valid) => *emoji = Some(valid),
+            Err(e) => {
+                error!("Invalid emoji: {}", e);
+                let response = default_response("Invalid emoji. Use a single emoji like ✅ or one of this server's custom emoji, or 'none'.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+    }
+    let [emoji, milestone_emoji] = emojis;
+    
+    let (feedback, changed) = {
+        let mut bot_data = data.write().await;
+        
+        // Get existing server config or create new one
+        let mut server_config = bot_data
+            .get_server_config(&guild_id)
+            .cloned()
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Apply whichever options were given; none at all just shows the feedback settings
+        let feedback = &mut server_config.checkin_feedback;
+        if let Some(emoji) = emoji {
+            feedback.emoji = emoji;
+        }
+        if let Some(emoji) = milestone_emoji {
+            feedback.milestone_emoji = emoji;
+        }
+        if let Ok(value) = get_boolean_option(command, "reply-with-streak") {
+            feedback.reply_with_streak = value;
+        }
+        
+        let feedback = server_config.checkin_feedback.clone();
+        let changed = bot_data
+            .get_server_config(&guild_id)
+            .map(|existing| existing.checkin_feedback != feedback)
+            .unwrap_or(feedback != CheckinFeedback::default());
+        
+        if changed {
+            server_config.updated_at = bot_data.now();
+            bot_data.add_or_update_server(server_config);
+            
+            // Persist to disk
+            if let Err(e) = bot_data.save().await {
+                error!("Failed to save data after updating check-in feedback: {}", e);
+                let response = default_response("Failed to save configuration. Please try again.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+        
+        (feedback, changed)
+    };
+    
+    if changed {
+        debug!("Updated check-in feedback for guild {}: {:?}", guild_id, feedback);
+    }
+    
+    let embed = CreateEmbed::new()
+        .title(if changed { "✅ Check-in Feedback Updated" } else { "📝 Check-in Feedback" })
+        .color(0x5865F2)
+        .field("👍 Reaction", feedback.emoji.as_deref().unwrap_or("None"), true)
+        .field("🏆 Milestone Reaction", feedback.milestone_emoji.as_deref().unwrap_or("Same as usual"), true)
+        .field("💬 Streak Reply", if feedback.reply_with_streak { "On" } else { "Off" }, true)
+        .footer(CreateEmbedFooter::new("Milestones are streaks of 7, 30, 50 and 365, and every 100"));
+    
+    command.create_response(&ctx.http, embed_response(embed)).await?;
+    Ok(())
+}
+
+pub fn manager_roles_command() -> CreateCommand {
+    CreateCommand::new("manager-roles")
+        .description("View or change which roles may use the admin commands (Admin only)")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Role,
+                "add",
+                "A role whose members may configure the bot"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Role,
+                "remove",
+                "A role that should no longer configure the bot"
+            )
+            .required(false)
+        )
+}
+
+pub async fn manager_roles(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    info!("Manager roles command executed by user {}", command.user.id);
+    
+    // Only administrators choose who else may configure the bot
+    if access(ctx, command, &data).await? < Access::Admin {
+        let response = default_response("This command requires administrator permissions.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    
+    // Get guild ID
+    let guild_id = get_guild_id(command)?;
+    
+    let (role_ids, changed) = {
+        let mut bot_data = data.write().await;
+        
+        // Get existing server config or create new one
+        let mut server_config = bot_data
+            .get_server_config(&guild_id)
+            .cloned()
+            .unwrap_or_else(|| ServerConfig::new(guild_id.clone(), bot_data.now()));
+        
+        // Apply whichever options were given; none at all just shows the manager roles
+        let role_ids = &mut server_config.manager_role_ids;
+        if let Ok(role_id) = get_role_option(command, "add") {
+            if !role_ids.contains(&role_id.to_string()) {
+                role_ids.push(role_id.to_string());
+            }
+        }
+        if let Ok(role_id) = get_role_option(command, "remove") {
+            role_ids.retain(|id| *id != role_id.to_string());
+        }
+        
+        let role_ids = server_config.manager_role_ids.clone();
+        let changed = bot_data
+            .get_server_config(&guild_id)
+            .map(|existing| existing.manager_role_ids != role_ids)
+            .unwrap_or(!role_ids.is_empty());
+        
+        if changed {
+            server_config.updated_at = bot_data.now();
+            bot_data.add_or_update_server(server_config);
+            
+            // Persist to disk
+            if let Err(e) = bot_data.save().await {
+                error!("Failed to save data after updating manager roles: {}", e);
+                let response = default_response("Failed to save configuration. Please try again.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+        
+        (role_ids, changed)
+    };
+    
+    if changed {
+        debug!("Updated manager roles for guild {}: {:?}", guild_id, role_ids);
+    }
+    
+    let mut embed = CreateEmbed::new()
+        .title(if changed { "✅ Manager Roles Updated" } else { "🛡️ Manager Roles" })
+        .color(0x5865F2)
+        .footer(CreateEmbedFooter::new("Managers can use every admin command except this one"));
+    if role_ids.is_empty() {
+        embed = embed.description("None - only administrators can configure the bot");
+    } else {
+        let roles: Vec<String> = role_ids.iter().map(|id| format!("<@&{}>", id)).collect();
+        embed = embed.description(roles.join("\n"));
+    }
+    
+    command.create_response(&ctx.http, embed_response(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use serenity::{
+    builder::{CreateActionRow, CreateAllowedMentions, CreateButton, CreateCommand, CreateCommandOption, CreateMessage},
+    model::{
+        application::{ButtonStyle, CommandDataOptionValue, CommandInteraction, CommandOptionType, ComponentInteraction},
+        id::ChannelId,
+    },
+    prelude::*,
+};
+use crate::{bot::SharedBotData, commands::user, streaks::{CheckinOutcome, StreakManager}, utils::{command_helpers, responses}};
+use tracing::{info, error};
+
+/// Custom id of the "Check in" button on daily posts
+pub const BUTTON_ID: &str = "checkin";
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("checkin")
+        .description("Check in for today without replying in the thread")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "note",
+                "What you did today - shared in the check-in thread"
+            )
+            .max_length(1000)
+            .required(false)
+        )
+        .add_option(user::goal_name_option("Only check in on this goal (defaults to all of them)"))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Attachment,
+                "proof",
+                "A photo or file showing your progress"
+            )
+            .required(false)
+        )
+}
+
+/// The "Check in" button attached to daily posts
+pub fn button_row() -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(BUTTON_ID)
+            .label("Check in")
+            .emoji('✅')
+            .style(ButtonStyle::Success),
+    ])
+}
+
+pub async fn run(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command.guild_id
+        .ok_or_else(|| serenity::Error::Other("This command can only be used in a server"))?;
+    let note = command_helpers::get_string_option(command, "note").ok();
+    let requested_name = command_helpers::get_string_option(command, "name").ok();
+    let proof_url = command.data.options.iter()
+        .find(|opt| opt.name == "proof")
+        .and_then(|opt| match &opt.value {
+            CommandDataOptionValue::Attachment(id) => command.data.resolved.attachments.get(id),
+            _ => None,
+        })
+        .map(|attachment| attachment.url.clone());
+
+    info!("Checkin command executed by user {}", command.user.id);
+
+    // A goal picked by name is passed on as a `#name` tag, like in a thread reply
+    let mut content = note.clone().unwrap_or_default();
+    if let Some(name) = requested_name {
+        let goal = {
+            let data_read = data.read().await;
+            data_read
+                .get_user(&guild_id.to_string(), &command.user.id.to_string())
+                .filter(|user| user.is_active)
+                .map(|user| user::select_goal(user, Some(&name)).map(|goal| goal.name.clone()))
+        };
+        match goal {
+            Some(Ok(name)) => content = format!("#{} {}", name, content),
+            Some(Err(message)) => {
+                command.create_response(&ctx.http, responses::ephemeral_response(&message)).await?;
+                return Ok(());
+            }
+            None => {}
+        }
+    }
+
+    let outcome = StreakManager::new(data.clone())
+        .checkin_directly(guild_id, command.user.id, &content, proof_url.iter().count())
+        .await;
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            error!("Failed to record check-in: {}", e);
+            command.create_response(&ctx.http, responses::ephemeral_response("Failed to record your check-in. Please try again.")).await?;
+            return Ok(());
+        }
+    };
+
+    // Share the note and proof in the thread so they read like any other reply
+    if let CheckinOutcome::Recorded { .. } = outcome {
+        let shared = [note.as_deref(), proof_url.as_deref()].into_iter().flatten().collect::<Vec<_>>().join("\n");
+        if !shared.is_empty() {
+            share_note(ctx, &data, &guild_id.to_string(), command.user.id.get(), &shared).await;
+        }
+    }
+
+    command.create_response(&ctx.http, responses::ephemeral_response(&outcome_message(&outcome))).await?;
+    Ok(())
+}
+
+pub async fn handle_button(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let Some(guild_id) = component.guild_id else {
+        return Ok(());
+    };
+
+    // Buttons on earlier posts stay visible, but only the current post takes check-ins
+    let is_current_post = {
+        let data_read = data.read().await;
+        data_read
+            .daily_posts
+            .get(&guild_id.to_string())
+            .map(|daily_post| daily_post.message_id == component.message.id.to_string())
+            .unwrap_or(false)
+    };
+    let outcome = if is_current_post {
+        StreakManager::new(data.clone())
+            .checkin_directly(guild_id, component.user.id, "", 0)
+            .await
+    } else {
+        Ok(CheckinOutcome::NoOpenCheckin)
+    };
+    let message = match outcome {
+        Ok(CheckinOutcome::Rejected(_)) => {
+            "This server asks for more than a button press - reply in the check-in thread or use `/checkin` with a note or proof.".to_string()
+        }
+        Ok(outcome) => outcome_message(&outcome),
+        Err(e) => {
+            error!("Failed to record check-in: {}", e);
+            "Failed to record your check-in. Please try again.".to_string()
+        }
+    };
+
+    component.create_response(&ctx.http, responses::ephemeral_response(&message)).await?;
+    Ok(())
+}
+
+/// What to tell the user about their check-in
+fn outcome_message(outcome: &CheckinOutcome) -> String {
+    match outcome {
+        CheckinOutcome::Recorded { goals, milestones } => {
+            format!("✅ Checked in!\n{}", StreakManager::describe_streaks(goals, milestones))
+        }
+        CheckinOutcome::AlreadyCheckedIn => "You've already checked in for today. 🎉".to_string(),
+        CheckinOutcome::NotRegistered => "You're not currently registered for daily check-ins. Use `/register-goal` to get started!".to_string(),
+        CheckinOutcome::NoOpenCheckin => "There's no check-in open right now - wait for the next daily post.".to_string(),
+        CheckinOutcome::Rejected(problems) => format!("❌ That didn't count as a check-in: {}.", problems.join("; ")),
+    }
+}
+
+/// Post a `/checkin` note in the current check-in thread, without pinging anyone
+async fn share_note(ctx: &Context, data: &SharedBotData, guild_id: &str, user_id: u64, note: &str) {
+    let thread_id = {
+        let data_read = data.read().await;
+        data_read
+            .daily_posts
+            .get(guild_id)
+            .and_then(|daily_post| daily_post.thread_id.as_ref())
+            .and_then(|thread_id| thread_id.parse::<u64>().ok())
+    };
+    let Some(thread_id) = thread_id else {
+        return;
+    };
+
+    let message = CreateMessage::new()
+        .content(format!("<@{}> checked in: {}", user_id, note))
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Err(e) = ChannelId::new(thread_id).send_message(&ctx.http, message).await {
+        error!("Failed to share check-in note in thread {}: {}", thread_id, e);
+    }
+}
@@ -0,0 +1,421 @@
+use serenity::{
+    builder::{
+        CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter,
+        CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+    model::application::{ButtonStyle, CommandInteraction, CommandOptionType, ComponentInteraction},
+    prelude::*,
+};
+use crate::{bot::SharedBotData, data::{BotData, GoalFrequency}, utils::command_helpers};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use tracing::{info, debug};
+
+/// Entries shown per leaderboard page
+const PAGE_SIZE: usize = 10;
+
+/// Prefix of the custom ids on the pagination buttons
+const BUTTON_PREFIX: &str = "leaderboard";
+
+/// What the leaderboard ranks users by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    CurrentStreak,
+    LongestStreak,
+    Checkins,
+}
+
+/// Which check-ins count towards `Mode::Checkins`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    AllTime,
+    Month,
+    Week,
+}
+
+impl Mode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "current" => Some(Self::CurrentStreak),
+            "longest" => Some(Self::LongestStreak),
+            "checkins" => Some(Self::Checkins),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CurrentStreak => "current",
+            Self::LongestStreak => "longest",
+            Self::Checkins => "checkins",
+        }
+    }
+}
+
+impl Period {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "all-time" => Some(Self::AllTime),
+            "month" => Some(Self::Month),
+            "week" => Some(Self::Week),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::AllTime => "all-time",
+            Self::Month => "month",
+            Self::Week => "week",
+        }
+    }
+
+    /// First day counted, or `None` for all time. Weeks start on Monday.
+    fn start(self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::AllTime => None,
+            Self::Month => today.with_day(1),
+            Self::Week => Some(today - Duration::days(today.weekday().num_days_from_monday() as i64)),
+        }
+    }
+}
+
+/// A ranked leaderboard line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub rank: usize,
+    pub user_id: String,
+    pub value: u32,
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("leaderboard")
+        .description("Compare streaks and check-ins across the server")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "mode",
+                "What to rank by (defaults to current streak)"
+            )
+            .add_string_choice("Current streak", "current")
+            .add_string_choice("Longest streak", "longest")
+            .add_string_choice("Check-ins", "checkins")
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "Which check-ins to count (check-ins mode only, defaults to all time)"
+            )
+            .add_string_choice("All time", "all-time")
+            .add_string_choice("This month", "month")
+            .add_string_choice("This week", "week")
+            .required(false)
+        )
+}
+
+pub async fn run(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    let mode = command_helpers::get_string_option(command, "mode")
+        .ok()
+        .and_then(|mode| Mode::parse(&mode))
+        .unwrap_or(Mode::CurrentStreak);
+    let period = command_helpers::get_string_option(command, "period")
+        .ok()
+        .and_then(|period| Period::parse(&period))
+        .unwrap_or(Period::AllTime);
+
+    info!("Leaderboard command executed by user {} ({:?}, {:?})", command.user.id, mode, period);
+
+    let message = {
+        let data_read = data.read().await;
+        render_page(&data_read, &guild_id, mode, period, 0)
+    };
+
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(message)).await?;
+    Ok(())
+}
+
+/// Handle a click on one of the pagination buttons
+pub async fn handle_button(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let Some(guild_id) = component.guild_id.map(|id| id.to_string()) else {
+        return Ok(());
+    };
+    let Some((mode, period, page)) = parse_button_id(&component.data.custom_id) else {
+        debug!("Ignoring unknown leaderboard button {}", component.data.custom_id);
+        return Ok(());
+    };
+
+    let message = {
+        let data_read = data.read().await;
+        render_page(&data_read, &guild_id, mode, period, page)
+    };
+
+    component.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message)).await?;
+    Ok(())
+}
+
+/// Whether a component custom id belongs to a leaderboard button
+pub fn is_leaderboard_button(custom_id: &str) -> bool {
+    custom_id.starts_with(BUTTON_PREFIX) && custom_id[BUTTON_PREFIX.len()..].starts_with(':')
+}
+
+fn button_id(mode: Mode, period: Period, page: usize) -> String {
+    format!("{}:{}:{}:{}", BUTTON_PREFIX, mode.as_str(), period.as_str(), page)
+}
+
+fn parse_button_id(custom_id: &str) -> Option<(Mode, Period, usize)> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != BUTTON_PREFIX {
+        return None;
+    }
+    let mode = Mode::parse(parts.next()?)?;
+    let period = Period::parse(parts.next()?)?;
+    let page = parts.next()?.parse().ok()?;
+    Some((mode, period, page))
+}
+
+/// Rank a guild's active users. Ties share a rank and are ordered by user id so pages stay stable.
+pub fn rank(data: &BotData, guild_id: &str, mode: Mode, period: Period, today: NaiveDate) -> Vec<Entry> {
+    let Some(users) = data.users.get(guild_id) else {
+        return Vec::new();
+    };
+
+    let checkin_counts: HashMap<&str, u32> = if mode == Mode::Checkins {
+        let records = match period.start(today) {
+            Some(start) => data.get_checkins_in_range(guild_id, start, today),
+            None => data.get_checkins(guild_id).iter().collect(),
+        };
+        // One record is kept per goal, so a day counts once however many goals it covered
+        let days: HashSet<(&str, NaiveDate)> = records
+            .into_iter()
+            .map(|record| (record.user_id.as_str(), record.checkin_date))
+            .collect();
+        let mut counts = HashMap::new();
+        for (user_id, _) in days {
+            *counts.entry(user_id).or_default() += 1;
+        }
+        counts
+    } else {
+        HashMap::new()
+    };
+
+    // Streaks are compared in days, so weekly goals (counted in weeks) don't take part;
+    // users with several daily goals are ranked by their best one
+    let mut scores: Vec<(&str, u32)> = users
+        .values()
+        .filter(|user| user.is_active)
+        .filter_map(|user| {
+            let daily_goals = user.active_goals().filter(|goal| goal.frequency == GoalFrequency::Daily);
+            let value = match mode {
+                Mode::CurrentStreak => daily_goals.map(|goal| goal.current_streak).max()?,
+                Mode::LongestStreak => daily_goals.map(|goal| goal.longest_streak).max()?,
+                Mode::Checkins => checkin_counts.get(user.user_id.as_str()).copied().unwrap_or(0),
+            };
+            Some((user.user_id.as_str(), value))
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut entries: Vec<Entry> = Vec::with_capacity(scores.len());
+    for (index, (user_id, value)) in scores.into_iter().enumerate() {
+        let rank = match entries.last() {
+            Some(previous) if previous.value == value => previous.rank,
+            _ => index + 1,
+        };
+        entries.push(Entry { rank, user_id: user_id.to_string(), value });
+    }
+    entries
+}
+
+/// Build the embed and buttons for one page of the leaderboard
+fn render_page(data: &BotData, guild_id: &str, mode: Mode, period: Period, page: usize) -> CreateInteractionResponseMessage {
+    let today = data.guild_today(guild_id);
+    let entries = rank(data, guild_id, mode, period, today);
+    let page_count = entries.len().div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(page_count - 1);
+
+    let title = match (mode, period) {
+        (Mode::CurrentStreak, _) => "🏆 Leaderboard - Current Streak",
+        (Mode::LongestStreak, _) => "🏆 Leaderboard - Longest Streak",
+        (Mode::Checkins, Period::AllTime) => "🏆 Leaderboard - Check-ins (All Time)",
+        (Mode::Checkins, Period::Month) => "🏆 Leaderboard - Check-ins (This Month)",
+        (Mode::Checkins, Period::Week) => "🏆 Leaderboard - Check-ins (This Week)",
+    };
+
+    let description = if entries.is_empty() {
+        "No one is registered yet. Use `/register-goal` to join!".to_string()
+    } else {
+        entries
+            .iter()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|entry| {
+                let place = match entry.rank {
+                    1 => "🥇".to_string(),
+                    2 => "🥈".to_string(),
+                    3 => "🥉".to_string(),
+                    rank => format!("**{}.**", rank),
+                };
+                let value = match mode {
+                    Mode::Checkins => format!("{} check-ins", entry.value),
+                    _ => format!("{} {}", entry.value, GoalFrequency::Daily.streak_unit()),
+                };
+                format!("{} <@{}> - {}", place, entry.user_id, value)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!("Page {}/{}", page + 1, page_count)))
+        .color(0xffd700); // Gold color
+
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(button_id(mode, period, page.saturating_sub(1)))
+            .label("◀ Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(button_id(mode, period, page + 1))
+            .label("Next ▶")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= page_count),
+    ]);
+
+    CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![buttons])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{CheckinRecord, Goal, UserData};
+    use chrono::Utc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, day).unwrap()
+    }
+
+    fn user(user_id: &str, current_streak: u32, longest_streak: u32) -> UserData {
+        let mut user = UserData::new(user_id.to_string(), Utc::now());
+        let mut goal = Goal::new("read".to_string(), "Read".to_string(), Utc::now());
+        goal.current_streak = current_streak;
+        goal.longest_streak = longest_streak;
+        user.goals.push(goal);
+        user
+    }
+
+    fn checkin(user_id: &str, day: NaiveDate) -> CheckinRecord {
+        CheckinRecord {
+            user_id: user_id.to_string(),
+            checkin_date: day,
+            message_id: None,
+            thread_id: None,
+            daily_post_id: None,
+            goal: Some("read".to_string()),
+            created_at: Utc::now(),
+            before: None,
+        }
+    }
+
+    fn guild() -> BotData {
+        let mut data = BotData::default();
+        data.add_or_update_user("1000".to_string(), user("1", 3, 20));
+        data.add_or_update_user("1000".to_string(), user("2", 10, 10));
+        data.add_or_update_user("1000".to_string(), user("3", 3, 5));
+        let mut inactive = user("4", 50, 50);
+        inactive.is_active = false;
+        data.add_or_update_user("1000".to_string(), inactive);
+
+        // 2025-11-17 is a Monday
+        data.add_checkin("1000".to_string(), checkin("1", date(3)));
+        data.add_checkin("1000".to_string(), checkin("1", date(10)));
+        data.add_checkin("1000".to_string(), checkin("3", date(17)));
+        data.add_checkin("1000".to_string(), checkin("3", date(18)));
+        data
+    }
+
+    fn ranking(entries: &[Entry]) -> Vec<(usize, &str, u32)> {
+        entries.iter().map(|entry| (entry.rank, entry.user_id.as_str(), entry.value)).collect()
+    }
+
+    #[test]
+    fn streak_ties_share_a_rank_and_inactive_users_are_left_out() {
+        let entries = rank(&guild(), "1000", Mode::CurrentStreak, Period::AllTime, date(18));
+        assert_eq!(ranking(&entries), vec![(1, "2", 10), (2, "1", 3), (2, "3", 3)]);
+
+        let entries = rank(&guild(), "1000", Mode::LongestStreak, Period::AllTime, date(18));
+        assert_eq!(ranking(&entries), vec![(1, "1", 20), (2, "2", 10), (3, "3", 5)]);
+    }
+
+    #[test]
+    fn streaks_only_rank_daily_goals() {
+        let mut data = guild();
+        let mut weekly = user("5", 30, 30);
+        weekly.goals[0].frequency = GoalFrequency::Weekly { times: 3 };
+        data.add_or_update_user("1000".to_string(), weekly);
+        let mut both = user("6", 1, 1);
+        let mut gym = Goal::new("gym".to_string(), "Gym".to_string(), Utc::now());
+        gym.frequency = GoalFrequency::Weekly { times: 2 };
+        gym.current_streak = 40;
+        both.goals.push(gym);
+        data.add_or_update_user("1000".to_string(), both);
+
+        let entries = rank(&data, "1000", Mode::CurrentStreak, Period::AllTime, date(18));
+        assert_eq!(ranking(&entries), vec![(1, "2", 10), (2, "1", 3), (2, "3", 3), (4, "6", 1)]);
+    }
+
+    #[test]
+    fn checkins_are_counted_per_period() {
+        let data = guild();
+
+        let entries = rank(&data, "1000", Mode::Checkins, Period::AllTime, date(18));
+        assert_eq!(ranking(&entries), vec![(1, "1", 2), (1, "3", 2), (3, "2", 0)]);
+
+        let entries = rank(&data, "1000", Mode::Checkins, Period::Week, date(18));
+        assert_eq!(ranking(&entries), vec![(1, "3", 2), (2, "1", 0), (2, "2", 0)]);
+
+        let entries = rank(&data, "1000", Mode::Checkins, Period::Month, date(9));
+        assert_eq!(ranking(&entries), vec![(1, "1", 1), (2, "2", 0), (2, "3", 0)]);
+    }
+
+    #[test]
+    fn users_with_several_goals_rank_by_their_best_and_count_each_day_once() {
+        let mut data = guild();
+        let mut user = data.get_user("1000", "3").unwrap().clone();
+        let mut exercise = Goal::new("exercise".to_string(), "Exercise".to_string(), Utc::now());
+        exercise.current_streak = 12;
+        user.goals.push(exercise);
+        data.add_or_update_user("1000".to_string(), user);
+        let mut record = checkin("3", date(17));
+        record.goal = Some("exercise".to_string());
+        data.add_checkin("1000".to_string(), record);
+
+        let entries = rank(&data, "1000", Mode::CurrentStreak, Period::AllTime, date(18));
+        assert_eq!(ranking(&entries)[0], (1, "3", 12));
+
+        let entries = rank(&data, "1000", Mode::Checkins, Period::Week, date(18));
+        assert_eq!(ranking(&entries)[0], (1, "3", 2));
+    }
+
+    #[test]
+    fn button_ids_round_trip() {
+        let id = button_id(Mode::Checkins, Period::Week, 3);
+        assert!(is_leaderboard_button(&id));
+        assert_eq!(parse_button_id(&id), Some((Mode::Checkins, Period::Week, 3)));
+        assert!(!is_leaderboard_button("leaderboards:current:all-time:0"));
+    }
+}
@@ -1,6 +1,8 @@
 pub mod ping;
 pub mod user;
 pub mod admin;
+pub mod leaderboard;
+pub mod checkin;
 
 use serenity::{
     model::{application::{Command, Interaction}},
@@ -15,8 +17,22 @@ pub async fn register_commands(ctx: &Context) -> serenity::Result<()> {
         user::edit_goal_command(),
         user::deregister_command(),
         user::stats_command(),
+        user::history_command(),
+        user::heatmap_command(),
+        user::freezes_command(),
+        user::pause_command(),
+        user::resume_command(),
+        user::rest_days_command(),
+        user::set_timezone_command(),
+        checkin::register(),
+        leaderboard::register(),
         admin::set_channel_command(),
         admin::set_checkin_time_command(),
+        admin::set_checkin_schedule_command(),
+        admin::streak_policy_command(),
+        admin::checkin_requirements_command(),
+        admin::checkin_feedback_command(),
+        admin::manager_roles_command(),
     ];
 
     Command::set_global_commands(&ctx.http, commands).await?;
@@ -28,19 +44,51 @@ pub async fn handle_command(
     interaction: &Interaction,
     data: SharedBotData,
 ) -> serenity::Result<()> {
-    if let Interaction::Command(command) = interaction {
-        match command.data.name.as_str() {
+    match interaction {
+        Interaction::Command(command) => match command.data.name.as_str() {
             "ping" => ping::run(ctx, command).await?,
             "register-goal" => user::register_goal(ctx, command, data).await?,
             "edit-goal" => user::edit_goal(ctx, command, data).await?,
             "deregister" => user::deregister(ctx, command, data).await?,
             "stats" => user::stats(ctx, command, data).await?,
+            "history" => user::history(ctx, command, data).await?,
+            "heatmap" => user::heatmap(ctx, command, data).await?,
+            "freezes" => user::freezes(ctx, command, data).await?,
+            "pause" => user::pause(ctx, command, data).await?,
+            "resume" => user::resume(ctx, command, data).await?,
+            "rest-days" => user::rest_days(ctx, command, data).await?,
+            "set-timezone" => user::set_timezone(ctx, command, data).await?,
+            "checkin" => checkin::run(ctx, command, data).await?,
+            "leaderboard" => leaderboard::run(ctx, command, data).await?,
             "set-checkin-channel" => admin::set_channel(ctx, command, data).await?,
             "set-checkin-time" => admin::set_checkin_time(ctx, command, data).await?,
+            "set-checkin-schedule" => admin::set_checkin_schedule(ctx, command, data).await?,
+            "streak-policy" => admin::streak_policy(ctx, command, data).await?,
+            "checkin-requirements" => admin::checkin_requirements(ctx, command, data).await?,
+            "checkin-feedback" => admin::checkin_feedback(ctx, command, data).await?,
+            "manager-roles" => admin::manager_roles(ctx, command, data).await?,
             _ => {
                 tracing::warn!("Unknown command: {}", command.data.name);
             }
+        },
+        Interaction::Autocomplete(command) => match command.data.name.as_str() {
+            "edit-goal" | "deregister" | "stats" | "history" | "heatmap" | "freezes" | "checkin" => {
+                user::autocomplete_goal(ctx, command, data).await?
+            }
+            _ => {
+                tracing::warn!("Unknown autocomplete: {}", command.data.name);
+            }
+        },
+        Interaction::Component(component) => {
+            if leaderboard::is_leaderboard_button(&component.data.custom_id) {
+                leaderboard::handle_button(ctx, component, data).await?;
+            } else if component.data.custom_id == checkin::BUTTON_ID {
+                checkin::handle_button(ctx, component, data).await?;
+            } else {
+                tracing::warn!("Unknown component: {}", component.data.custom_id);
+            }
         }
+        _ => {}
     }
     Ok(())
 }
\ No newline at end of file
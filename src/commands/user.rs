@@ -1,12 +1,21 @@
 use serenity::{
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed},
-    model::application::{CommandInteraction, CommandOptionType},
+    builder::{CreateAttachment, CreateAutocompleteResponse, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage},
+    model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType},
     prelude::*,
 };
-use crate::{bot::SharedBotData, data::UserData, utils::{command_helpers, responses}};
-use chrono::Utc;
+use crate::{bot::SharedBotData, data::{goal_slug, BotData, FreezeEventKind, Goal, GoalFrequency, PausePeriod, RestDays, UserData, MAX_GOALS, MAX_GOAL_NAME_LENGTH, MAX_REST_DATES}, heatmap, history, utils::{command_helpers, responses}};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use std::collections::HashSet;
 use tracing::{info, error};
 
+/// The optional `name` option picking one of a user's goals, with autocomplete
+pub fn goal_name_option(description: &str) -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "name", description)
+        .max_length(MAX_GOAL_NAME_LENGTH as u16)
+        .set_autocomplete(true)
+        .required(false)
+}
+
 pub fn register_goal_command() -> CreateCommand {
     CreateCommand::new("register-goal")
         .description("Register a personal goal for daily check-ins")
@@ -19,25 +28,64 @@ pub fn register_goal_command() -> CreateCommand {
             .required(true)
             .max_length(500)
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "name",
+                "A short name to tag check-ins with, like 'exercise' (defaults to the goal's first word)"
+            )
+            .max_length(MAX_GOAL_NAME_LENGTH as u16)
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "times-per-week",
+                "Make this a weekly goal needing this many check-ins a week (defaults to daily)"
+            )
+            .min_int_value(1)
+            .max_int_value(7)
+            .required(false)
+        )
 }
 
 pub fn edit_goal_command() -> CreateCommand {
     CreateCommand::new("edit-goal")
-        .description("Edit your existing goal")
+        .description("Edit one of your existing goals")
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::String,
                 "goal",
                 "Your updated goal or objective"
             )
-            .required(true)
+            .required(false)
             .max_length(500)
         )
+        .add_option(goal_name_option("The goal to edit (needed if you have several)"))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "times-per-week",
+                "Check-ins needed per week, or 0 for a daily goal (changing this resets the streak)"
+            )
+            .min_int_value(0)
+            .max_int_value(7)
+            .required(false)
+        )
+}
+
+/// The frequency picked by a `times-per-week` option, where 0 means daily
+fn frequency_option(command: &CommandInteraction) -> Option<GoalFrequency> {
+    command_helpers::get_integer_option(command, "times-per-week").ok().map(|times| match times {
+        0 => GoalFrequency::Daily,
+        times => GoalFrequency::Weekly { times: times.clamp(1, 7) as u32 },
+    })
 }
 
 pub fn deregister_command() -> CreateCommand {
     CreateCommand::new("deregister")
-        .description("Remove yourself from daily check-ins")
+        .description("Remove a goal, or yourself, from daily check-ins")
+        .add_option(goal_name_option("The goal to remove (defaults to all of them)"))
 }
 
 pub fn stats_command() -> CreateCommand {
@@ -51,6 +99,210 @@ pub fn stats_command() -> CreateCommand {
             )
             .required(false)
         )
+        .add_option(goal_name_option("Only show this goal (defaults to all of them)"))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "calendar",
+                "Include this month's check-in calendar"
+            )
+            .required(false)
+        )
+}
+
+pub fn heatmap_command() -> CreateCommand {
+    CreateCommand::new("heatmap")
+        .description("View a year of check-ins as a heatmap image for yourself or another user")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "user",
+                "The user to view the heatmap for (defaults to yourself)"
+            )
+            .required(false)
+        )
+        .add_option(goal_name_option("The goal to show (needed if there are several)"))
+}
+
+pub fn history_command() -> CreateCommand {
+    CreateCommand::new("history")
+        .description("View a check-in calendar and completion rates for yourself or another user")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "user",
+                "The user to view history for (defaults to yourself)"
+            )
+            .required(false)
+        )
+        .add_option(goal_name_option("The goal to show (needed if there are several)"))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "month",
+                "The last month to show (YYYY-MM, defaults to this month)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "months",
+                "How many months to show, counting back (defaults to 1)"
+            )
+            .min_int_value(1)
+            .max_int_value(3)
+            .required(false)
+        )
+}
+
+/// Longest pause a user can schedule at once
+const MAX_PAUSE_DAYS: i64 = 90;
+
+pub fn pause_command() -> CreateCommand {
+    CreateCommand::new("pause")
+        .description("Take a break from daily check-ins without losing your streak")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "from",
+                "First day of your break (YYYY-MM-DD)"
+            )
+            .required(true)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "to",
+                "Last day of your break (YYYY-MM-DD)"
+            )
+            .required(true)
+        )
+}
+
+pub fn resume_command() -> CreateCommand {
+    CreateCommand::new("resume")
+        .description("End your break early and cancel any scheduled pauses")
+}
+
+pub fn rest_days_command() -> CreateCommand {
+    CreateCommand::new("rest-days")
+        .description("View or change the days you rest, which never break your streak")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "weekdays",
+                "Days of the week you rest, like 'sat sun', or 'none'"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "add-date",
+                "A single day off (YYYY-MM-DD)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "remove-date",
+                "Cancel a single day off (YYYY-MM-DD)"
+            )
+            .required(false)
+        )
+}
+
+pub fn set_timezone_command() -> CreateCommand {
+    CreateCommand::new("set-timezone")
+        .description("View or change the timezone your check-in days are counted in")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "timezone",
+                "Your timezone, like 'Asia/Tokyo', or 'server' to use the server's"
+            )
+            .max_length(64)
+            .required(false)
+        )
+}
+
+pub fn freezes_command() -> CreateCommand {
+    CreateCommand::new("freezes")
+        .description("View your streak freeze balance and history")
+        .add_option(goal_name_option("Only show this goal (defaults to all of them)"))
+}
+
+/// Comma-separated `name`s of a user's active goals
+fn goal_names(user: &UserData) -> String {
+    user.active_goals()
+        .map(|goal| format!("`{}`", goal.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The goal picked by the `name` option, or the user's only active goal when it's left out.
+/// The error is a message for the user.
+pub fn select_goal<'a>(user: &'a UserData, name: Option<&str>) -> Result<&'a Goal, String> {
+    match name {
+        Some(name) => user
+            .goal(&name.to_lowercase())
+            .filter(|goal| goal.is_active)
+            .ok_or_else(|| format!("There's no active goal named `{}`. Goals: {}", name, goal_names(user))),
+        None => {
+            let mut goals = user.active_goals();
+            match (goals.next(), goals.next()) {
+                (Some(goal), None) => Ok(goal),
+                (None, _) => Err("There are no active goals. Use `/register-goal` to add one!".to_string()),
+                _ => Err(format!("Pick a goal with the `name` option: {}", goal_names(user))),
+            }
+        }
+    }
+}
+
+/// The user a command is about: the `user` option if given, otherwise whoever ran it
+fn target_user(command: &CommandInteraction) -> (String, bool) {
+    command.data.options.iter()
+        .find(|opt| opt.name == "user")
+        .and_then(|opt| match &opt.value {
+            CommandDataOptionValue::User(user_id) => Some((user_id.to_string(), *user_id == command.user.id)),
+            _ => None,
+        })
+        .unwrap_or_else(|| (command_helpers::get_user_id(command), true))
+}
+
+/// Suggest goal names for the focused `name` option, from the `user` option's goals or the caller's own
+pub async fn autocomplete_goal(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command_helpers::get_guild_id(command)?;
+    let (user_id, _) = target_user(command);
+    let prefix = command.data.autocomplete().map(|option| option.value.to_lowercase()).unwrap_or_default();
+
+    let mut response = CreateAutocompleteResponse::new();
+    {
+        let data_read = data.read().await;
+        if let Some(user) = data_read.get_user(&guild_id, &user_id) {
+            for goal in user.active_goals().filter(|goal| goal.name.starts_with(&prefix)).take(25) {
+                response = response.add_string_choice(format!("{} - {}", goal.name, truncate(&goal.description, 60)), &goal.name);
+            }
+        }
+    }
+
+    command.create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response)).await?;
+    Ok(())
+}
+
+/// Shorten `text` to at most `max` characters, marking the cut with an ellipsis
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        format!("{}…", text.chars().take(max - 1).collect::<String>())
+    } else {
+        text.to_string()
+    }
 }
 
 pub async fn register_goal(
@@ -61,81 +313,133 @@ pub async fn register_goal(
     // Extract context and arguments using helper functions with ? operator
     let user_id = command_helpers::get_user_id(command);
     let guild_id = command_helpers::get_guild_id(command)?;
-    let goal = command_helpers::get_string_option(command, "goal")?;
+    let description = command_helpers::get_string_option(command, "goal")?;
 
     info!("Register goal command executed by user {}", user_id);
 
     // Validate goal length
-    if goal.len() > 500 {
+    if description.len() > 500 {
         let response = responses::default_response("Goal must be 500 characters or less.");
         command.create_response(&ctx.http, response).await?;
         return Ok(());
     }
 
-    let now = Utc::now();
-    let is_update;
-
-    // Update or create user data
-    {
-        let mut data_write = data.write().await;
-        
-        if let Some(existing_user) = data_write.get_user_mut(&guild_id, &user_id) {
-            if existing_user.is_active {
-                // Update existing active user - preserve all streak data
-                existing_user.goal = goal.clone();
-                existing_user.updated_at = now;
-                is_update = true;
-            } else {
-                // Reactivate inactive user - reset streak, optionally update goal
-                existing_user.goal = goal.clone();
-                existing_user.current_streak = 0;
-                existing_user.last_checkin_date = None;
-                existing_user.grace_period_start = None;
-                existing_user.is_active = true;
-                existing_user.updated_at = now;
-                is_update = false; // Treat as new registration for messaging
+    let requested_name = match command_helpers::get_string_option(command, "name") {
+        Ok(name) => match command_helpers::validate_goal_name(&name) {
+            Ok(name) => Some(name),
+            Err(_) => {
+                let response = responses::default_response("Invalid goal name. Use up to 20 letters, digits or dashes (e.g., 'exercise', 'spanish-2').");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
             }
-        } else {
-            // Create new user
-            let user_data = UserData {
-                user_id: user_id.clone(),
-                goal: goal.clone(),
-                current_streak: 0,
-                longest_streak: 0,
-                last_checkin_date: None,
-                grace_period_start: None,
-                is_active: true,
-                created_at: now,
-                updated_at: now,
-            };
-            data_write.add_or_update_user(guild_id.clone(), user_data);
-            is_update = false;
-        }
-        
-        if let Err(e) = data_write.save().await {
+        },
+        Err(_) => None,
+    };
+
+    let frequency = frequency_option(command).unwrap_or_default();
+    let response = match add_goal(&data, &guild_id, &user_id, description, requested_name, frequency).await {
+        Ok(GoalRegistration::Saved(message)) => responses::default_response(&message),
+        Ok(GoalRegistration::TooManyGoals) => responses::ephemeral_response(&format!(
+            "You can have at most {} goals. Remove one with `/deregister` first, or update an existing goal by giving its `name`.",
+            MAX_GOALS
+        )),
+        Err(e) => {
             error!("Failed to save user data: {}", e);
-            let response = responses::default_response("Failed to save your goal. Please try again.");
-            command.create_response(&ctx.http, response).await?;
-            return Ok(());
+            responses::default_response("Failed to save your goal. Please try again.")
         }
-    }
-
-    // Send success response
-    let message = if is_update {
-        format!("Your goal has been updated to: \"{}\"", goal)
-    } else {
-        format!("Your goal has been set to: **{}**\n\nYou'll be pinged for daily check-ins to track your progress!", goal)
     };
 
-    let response = responses::default_response(&message);
     command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// What `add_goal` did
+#[derive(Debug, PartialEq)]
+pub enum GoalRegistration {
+    Saved(String), // the confirmation to show the member
+    TooManyGoals,  // the member already has `MAX_GOALS` active goals; nothing changed
+}
+
+/// Add a goal for a member, or update their goal named `requested_name`.
+/// `description` and `requested_name` are already validated.
+pub async fn add_goal(
+    data: &SharedBotData,
+    guild_id: &str,
+    user_id: &str,
+    description: String,
+    requested_name: Option<String>,
+    frequency: GoalFrequency,
+) -> anyhow::Result<GoalRegistration> {
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+
+    let mut user = data_write
+        .get_user(guild_id, user_id)
+        .cloned()
+        .unwrap_or_else(|| UserData::new(user_id.to_string(), now));
 
-    info!("Successfully {} goal for user {} in guild {}", 
+    // A named goal is updated in place; otherwise the goal's first word names it,
+    // reviving a removed goal of that name or picking a fresh one
+    let name = match requested_name {
+        Some(name) => name,
+        None => {
+            let slug = goal_slug(&description);
+            match user.goal(&slug) {
+                Some(goal) if !goal.is_active => slug,
+                _ => user.unused_goal_name(&slug),
+            }
+        }
+    };
+
+    // Adding or reviving a goal must stay within the limit
+    let is_active = user.goal(&name).is_some_and(|goal| goal.is_active);
+    if !is_active && user.active_goals().count() >= MAX_GOALS {
+        return Ok(GoalRegistration::TooManyGoals);
+    }
+
+    // Update or create the goal
+    let is_update = match user.goal_mut(&name) {
+        Some(existing) if existing.is_active => {
+            // Update existing active goal - preserve all streak data unless the frequency changes
+            existing.description = description.clone();
+            existing.set_frequency(frequency);
+            existing.updated_at = now;
+            true
+        }
+        Some(existing) => {
+            // Reactivate removed goal - reset streak, update description
+            existing.reactivate(description.clone(), frequency, now);
+            false // Treat as new registration for messaging
+        }
+        None => {
+            let mut goal = Goal::new(name.clone(), description.clone(), now);
+            goal.frequency = frequency;
+            user.goals.push(goal);
+            false
+        }
+    };
+    user.is_active = true;
+    user.updated_at = now;
+    let goal_count = user.active_goals().count();
+    data_write.add_or_update_user(guild_id.to_string(), user);
+    data_write.save().await?;
+
+    info!("Successfully {} goal {} for user {} in guild {}", 
           if is_update { "updated" } else { "registered" }, 
+          name,
           user_id, 
           guild_id);
 
-    Ok(())
+    let message = if is_update {
+        format!("Your goal `{}` has been updated to: \"{}\"", name, description)
+    } else if let GoalFrequency::Weekly { times } = frequency {
+        format!("Added weekly goal `{}`: **{}**\n\nCheck in {} times a week to keep the streak going - weeks run Monday to Sunday.", name, description, times)
+    } else if goal_count > 1 {
+        format!("Added goal `{}`: **{}**\n\nYou now have {} goals. Check-ins count for all of them, or tag a goal like `#{}` to check in on just that one.", name, description, goal_count, name)
+    } else {
+        format!("Your goal has been set to: **{}**\n\nYou'll be pinged for daily check-ins to track your progress!", description)
+    };
+    Ok(GoalRegistration::Saved(message))
 }
 
 pub async fn edit_goal(
@@ -143,8 +447,74 @@ pub async fn edit_goal(
     command: &CommandInteraction,
     data: SharedBotData,
 ) -> serenity::Result<()> {
-    // /edit-goal is an alias for /register-goal - same functionality, clearer intent
-    register_goal(ctx, command, data).await
+    let user_id = command_helpers::get_user_id(command);
+    let guild_id = command_helpers::get_guild_id(command)?;
+    let description = command_helpers::get_string_option(command, "goal").ok();
+    let frequency = frequency_option(command);
+    let requested_name = command_helpers::get_string_option(command, "name").ok();
+
+    info!("Edit goal command executed by user {}", user_id);
+
+    if description.is_none() && frequency.is_none() {
+        let response = responses::default_response("Give a new `goal` text, a new `times-per-week`, or both.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+    if description.as_ref().is_some_and(|description| description.len() > 500) {
+        let response = responses::default_response("Goal must be 500 characters or less.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+
+    let name = match data_write.get_user(&guild_id, &user_id) {
+        Some(user) if user.is_active => select_goal(user, requested_name.as_deref()).map(|goal| goal.name.clone()),
+        _ => Err("You're not currently registered for daily check-ins. Use `/register-goal` to get started!".to_string()),
+    };
+    let name = match name {
+        Ok(name) => name,
+        Err(message) => {
+            let response = responses::default_response(&message);
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let mut changes = Vec::new();
+    if let Some(goal) = data_write.get_user_mut(&guild_id, &user_id).and_then(|user| user.goal_mut(&name)) {
+        // Editing the text keeps all streak data; a new frequency starts the streak over
+        if let Some(description) = &description {
+            goal.description = description.clone();
+            changes.push(format!("updated to: \"{}\"", description));
+        }
+        if let Some(frequency) = frequency {
+            if frequency != goal.frequency {
+                goal.set_frequency(frequency);
+                changes.push(format!("now {} (streak reset)", frequency.describe().to_lowercase()));
+            }
+        }
+        goal.updated_at = now;
+    }
+
+    if let Err(e) = data_write.save().await {
+        error!("Failed to save user data: {}", e);
+        let response = responses::default_response("Failed to save your goal. Please try again.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let message = if changes.is_empty() {
+        format!("Your goal `{}` is unchanged.", name)
+    } else {
+        format!("Your goal `{}` is {}", name, changes.join(", and "))
+    };
+    let response = responses::default_response(&message);
+    command.create_response(&ctx.http, response).await?;
+
+    info!("Successfully updated goal {} for user {} in guild {}", name, user_id, guild_id);
+    Ok(())
 }
 
 pub async fn deregister(
@@ -155,12 +525,14 @@ pub async fn deregister(
     // Extract context using helper functions
     let user_id = command_helpers::get_user_id(command);
     let guild_id = command_helpers::get_guild_id(command)?;
+    let requested_name = command_helpers::get_string_option(command, "name").ok();
 
     info!("Deregister command executed by user {}", user_id);
 
-    // Deactivate user (preserve data for potential re-registration)
+    // Deactivate goals (preserve data for potential re-registration)
     {
         let mut data_write = data.write().await;
+        let now = data_write.now();
         
         let existing_user = data_write.get_user_mut(&guild_id, &user_id)
             .ok_or_else(|| serenity::Error::Other("You're not currently registered for daily check-ins"))?;
@@ -169,9 +541,33 @@ pub async fn deregister(
             return Err(serenity::Error::Other("You're not currently registered for daily check-ins"));
         }
         
-        let current_streak = existing_user.current_streak;
-        existing_user.is_active = false;
-        existing_user.updated_at = Utc::now();
+        // Name, streak and streak unit of each goal removed
+        let removed: Vec<(String, u32, &str)> = match &requested_name {
+            Some(name) => match select_goal(existing_user, Some(name)) {
+                Ok(goal) => vec![(goal.name.clone(), goal.current_streak, goal.frequency.streak_unit())],
+                Err(message) => {
+                    let response = responses::default_response(&message);
+                    command.create_response(&ctx.http, response).await?;
+                    return Ok(());
+                }
+            },
+            None => existing_user
+                .active_goals()
+                .map(|goal| (goal.name.clone(), goal.current_streak, goal.frequency.streak_unit()))
+                .collect(),
+        };
+        for (name, _, _) in &removed {
+            if let Some(goal) = existing_user.goal_mut(name) {
+                goal.is_active = false;
+                goal.updated_at = now;
+            }
+        }
+        // Without active goals there's nothing left to check in on
+        let fully_removed = existing_user.active_goals().next().is_none();
+        if fully_removed {
+            existing_user.is_active = false;
+        }
+        existing_user.updated_at = now;
         
         if let Err(e) = data_write.save().await {
             error!("Failed to save user data: {}", e);
@@ -180,100 +576,505 @@ pub async fn deregister(
             return Ok(());
         }
 
-        let message = format!("You have been removed from daily check-ins. Your streak was {} days. Use `/register-goal` to re-register later if you'd like.", current_streak);
+        let message = match (removed.as_slice(), fully_removed) {
+            ([(_, streak, unit)], true) => format!("You have been removed from daily check-ins. Your streak was {} {}. Use `/register-goal` to re-register later if you'd like.", streak, unit),
+            ([(name, streak, unit)], false) => format!("Removed goal `{}`. Its streak was {} {}. Your other goals are unaffected.", name, streak, unit),
+            (goals, _) => format!(
+                "You have been removed from daily check-ins. Your streaks were: {}. Use `/register-goal` to re-register later if you'd like.",
+                goals.iter().map(|(name, streak, unit)| format!("`{}` {} {}", name, streak, unit)).collect::<Vec<_>>().join(", ")
+            ),
+        };
         let response = responses::default_response(&message);
         command.create_response(&ctx.http, response).await?;
 
-        info!("Successfully deactivated user {} in guild {}", user_id, guild_id);
+        info!("Successfully deactivated {} goal(s) of user {} in guild {}", removed.len(), user_id, guild_id);
     }
 
     Ok(())
 }
 
-pub async fn stats(
+pub async fn pause(
     ctx: &Context,
     command: &CommandInteraction,
     data: SharedBotData,
 ) -> serenity::Result<()> {
-    use chrono::Duration;
-    use serenity::model::application::CommandDataOptionValue;
-
+    let user_id = command_helpers::get_user_id(command);
     let guild_id = command_helpers::get_guild_id(command)?;
 
-    // Check if a user parameter was provided, otherwise use the command user
-    let (target_user_id, is_self) = command.data.options.iter()
-        .find(|opt| opt.name == "user")
-        .and_then(|opt| match &opt.value {
-            CommandDataOptionValue::User(user_id) => Some((user_id.to_string(), *user_id == command.user.id)),
-            _ => None,
-        })
-        .unwrap_or_else(|| (command_helpers::get_user_id(command), true));
+    info!("Pause command executed by user {}", user_id);
 
-    info!("Stats command executed by user {} for user {}", command_helpers::get_user_id(command), target_user_id);
+    let from = command_helpers::get_string_option(command, "from")
+        .ok()
+        .and_then(|from| command_helpers::validate_date(&from).ok());
+    let to = command_helpers::get_string_option(command, "to")
+        .ok()
+        .and_then(|to| command_helpers::validate_date(&to).ok());
+    let (from, to) = match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => {
+            let response = responses::default_response("Invalid date. Use YYYY-MM-DD format (e.g., '2025-12-24').");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
 
-    // Get user data
-    let data_read = data.read().await;
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+    let today = data_write.user_today(&guild_id, &user_id);
 
-    let user = match data_read.get_user(&guild_id, &target_user_id) {
+    let problem = if to < from {
+        Some("The end of your break can't be before its start.".to_string())
+    } else if from < today {
+        Some("Breaks can't start in the past.".to_string())
+    } else if (to - from).num_days() + 1 > MAX_PAUSE_DAYS {
+        Some(format!("Breaks can be at most {} days long.", MAX_PAUSE_DAYS))
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        let response = responses::default_response(&problem);
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let user = match data_write.get_user_mut(&guild_id, &user_id) {
         Some(user) if user.is_active => user,
         _ => {
-            let msg = if is_self {
-                "You're not currently registered for daily check-ins. Use `/register-goal` to get started!"
-            } else {
-                "That user is not currently registered for daily check-ins."
-            };
-            let response = responses::default_response(msg);
+            let response = responses::default_response("You're not currently registered for daily check-ins. Use `/register-goal` to get started!");
             command.create_response(&ctx.http, response).await?;
             return Ok(());
         }
     };
 
-    // Build the stats embed
-    let title = if is_self {
-        "📊 Your Stats"
-    } else {
-        "📊 User Stats"
+    // Pauses that ended before the last check-in no longer affect the streak
+    let last_checkin = user.goals.iter().filter_map(|goal| goal.last_checkin_date).max();
+    user.pauses.retain(|pause| last_checkin.map(|last| pause.to >= last).unwrap_or(true));
+    user.pauses.push(PausePeriod { from, to });
+    user.pauses.sort_by_key(|pause| pause.from);
+    user.updated_at = now;
+    let streaks = match select_goal(user, None) {
+        Ok(goal) => format!("your streak of {} {}", goal.current_streak, goal.frequency.streak_unit()),
+        Err(_) => "your streaks".to_string(),
     };
 
-    let mut embed = CreateEmbed::new()
-        .title(title)
-        .color(0x00d4ff); // Light blue color
-
-    // Add user mention if not self
-    if !is_self {
-        embed = embed.description(format!("<@{}>", target_user_id));
+    if let Err(e) = data_write.save().await {
+        error!("Failed to save pause: {}", e);
+        let response = responses::default_response("Failed to save your break. Please try again.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
     }
 
-    // Goal field
-    embed = embed.field("🎯 Goal", &user.goal, false);
+    let message = format!(
+        "Enjoy your break! You won't be pinged from {} to {}, and {} will be waiting when you get back. Use `/resume` to come back early.",
+        from, to, streaks
+    );
+    let response = responses::default_response(&message);
+    command.create_response(&ctx.http, response).await?;
 
-    // Streak fields
-    embed = embed
-        .field("🔥 Current Streak", format!("{} days", user.current_streak), true)
-        .field("🏆 Longest Streak", format!("{} days", user.longest_streak), true);
+    info!("User {} paused check-ins in guild {} from {} to {}", user_id, guild_id, from, to);
+    Ok(())
+}
+
+pub async fn resume(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let user_id = command_helpers::get_user_id(command);
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    info!("Resume command executed by user {}", user_id);
+
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+    let today = data_write.user_today(&guild_id, &user_id);
+
+    let user = match data_write.get_user_mut(&guild_id, &user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let response = responses::default_response("You're not currently registered for daily check-ins. Use `/register-goal` to get started!");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    if !user.pauses.iter().any(|pause| pause.to >= today) {
+        let response = responses::default_response("You don't have a break in progress or scheduled.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    // Keep the days already taken off; drop today onwards
+    let yesterday = today - Duration::days(1);
+    user.pauses.retain(|pause| pause.from < today);
+    for pause in user.pauses.iter_mut() {
+        pause.to = pause.to.min(yesterday);
+    }
+    user.updated_at = now;
+
+    if let Err(e) = data_write.save().await {
+        error!("Failed to save resume: {}", e);
+        let response = responses::default_response("Failed to end your break. Please try again.");
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let response = responses::default_response("Welcome back! You'll be included in check-ins again starting with the next post.");
+    command.create_response(&ctx.http, response).await?;
+
+    info!("User {} resumed check-ins in guild {}", user_id, guild_id);
+    Ok(())
+}
+
+pub async fn rest_days(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let user_id = command_helpers::get_user_id(command);
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    info!("Rest days command executed by user {}", user_id);
+
+    let weekdays = match command_helpers::get_string_option(command, "weekdays") {
+        Ok(text) => match command_helpers::parse_weekdays(&text) {
+            Ok(weekdays) => Some(weekdays),
+            Err(_) => {
+                let response = responses::default_response("Invalid weekdays. Use names like 'sat sun' or 'Saturday, Sunday', or 'none'.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => None,
+    };
+    let mut dates = [None, None];
+    for (slot, option) in dates.iter_mut().zip(["add-date", "remove-date"]) {
+        if let Ok(text) = command_helpers::get_string_option(command, option) {
+            match command_helpers::validate_date(&text) {
+                Ok(date) => *slot = Some(date),
+                Err(_) => {
+                    let response = responses::default_response("Invalid date. Use YYYY-MM-DD format (e.g., '2025-12-24').");
+                    command.create_response(&ctx.http, response).await?;
+                    return Ok(());
+                }
+            }
+        }
+    }
+    let [add_date, remove_date] = dates;
+
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+    let today = data_write.user_today(&guild_id, &user_id);
+
+    let user = match data_write.get_user_mut(&guild_id, &user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let response = responses::default_response("You're not currently registered for daily check-ins. Use `/register-goal` to get started!");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    // Dates before the last check-in no longer affect any streak
+    let last_checkin = user.goals.iter().filter_map(|goal| goal.last_checkin_date).max();
+    let mut rest_days = user.rest_days.clone();
+    rest_days.dates.retain(|date| last_checkin.map(|last| *date >= last).unwrap_or(true));
+
+    let problem = if weekdays.as_ref().is_some_and(|weekdays| weekdays.len() == 7) {
+        Some("You need at least one check-in day a week.".to_string())
+    } else if add_date.is_some_and(|date| date < today) {
+        Some("Rest days can't be in the past.".to_string())
+    } else if remove_date.is_some_and(|date| !rest_days.dates.contains(&date)) {
+        Some("That date isn't one of your rest days.".to_string())
+    } else if add_date.is_some() && rest_days.dates.iter().filter(|date| **date >= today).count() >= MAX_REST_DATES {
+        Some(format!("You can have at most {} single rest days scheduled.", MAX_REST_DATES))
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        let response = responses::default_response(&problem);
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    if let Some(weekdays) = weekdays {
+        rest_days.weekdays = weekdays;
+    }
+    if let Some(date) = add_date {
+        if let Err(index) = rest_days.dates.binary_search(&date) {
+            rest_days.dates.insert(index, date);
+        }
+    }
+    if let Some(date) = remove_date {
+        rest_days.dates.retain(|rest_date| *rest_date != date);
+    }
+
+    let changed = rest_days != user.rest_days;
+    let schedule = rest_days.describe();
+    if changed {
+        user.rest_days = rest_days;
+        user.updated_at = now;
+
+        if let Err(e) = data_write.save().await {
+            error!("Failed to save rest days: {}", e);
+            let response = responses::default_response("Failed to save your rest days. Please try again.");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+        info!("User {} changed rest days in guild {} to {}", user_id, guild_id, schedule);
+    }
+
+    let message = format!(
+        "💤 **Rest days:** {}\n\nYou won't be pinged on rest days, and skipping them never breaks a streak. Checking in anyway still counts.",
+        schedule
+    );
+    let response = responses::default_response(&message);
+    command.create_response(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+pub async fn set_timezone(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let user_id = command_helpers::get_user_id(command);
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    info!("Set timezone command executed by user {}", user_id);
+
+    // None leaves the timezone as it is; Some(None) follows the server again
+    let timezone = match command_helpers::get_string_option(command, "timezone") {
+        Ok(text) if text.trim().eq_ignore_ascii_case("server") => Some(None),
+        Ok(text) => match command_helpers::validate_timezone(text.trim()) {
+            Ok(timezone) => Some(Some(timezone)),
+            Err(_) => {
+                let response = responses::default_response("Invalid timezone. Use format like 'America/New_York', 'Europe/London', or 'UTC', or 'server'.");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => None,
+    };
+
+    let mut data_write = data.write().await;
+    let now = data_write.now();
+    let guild_timezone = data_write.guild_timezone(&guild_id);
+
+    let user = match data_write.get_user_mut(&guild_id, &user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let response = responses::default_response("You're not currently registered for daily check-ins. Use `/register-goal` to get started!");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let changed = timezone.as_ref().is_some_and(|timezone| *timezone != user.timezone);
+    if let Some(timezone) = timezone.filter(|_| changed) {
+        user.timezone = timezone;
+        user.updated_at = now;
+    }
+    let source = if user.timezone.is_some() { "your own" } else { "the server's" };
+    let timezone = user.timezone_or(guild_timezone);
+
+    if changed {
+        if let Err(e) = data_write.save().await {
+            error!("Failed to save timezone: {}", e);
+            let response = responses::default_response("Failed to save your timezone. Please try again.");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+        info!("User {} set their timezone in guild {} to {} ({})", user_id, guild_id, timezone, source);
+    }
+
+    let message = format!(
+        "🌐 **Timezone:** {} ({}) - it's {} there.\n\nYour check-ins count for the day they're posted on in this timezone, and a day only counts as missed once it's over there.",
+        timezone,
+        source,
+        now.with_timezone(&timezone).format("%H:%M on %a %m/%d"),
+    );
+    let response = responses::default_response(&message);
+    command.create_response(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+pub async fn stats(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    // Check if a user parameter was provided, otherwise use the command user
+    let (target_user_id, is_self) = target_user(command);
+
+    info!("Stats command executed by user {} for user {}", command_helpers::get_user_id(command), target_user_id);
+
+    // Get user data
+    let data_read = data.read().await;
+
+    let user = match data_read.get_user(&guild_id, &target_user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let msg = if is_self {
+                "You're not currently registered for daily check-ins. Use `/register-goal` to get started!"
+            } else {
+                "That user is not currently registered for daily check-ins."
+            };
+            let response = responses::default_response(msg);
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    // One goal if a name was given, otherwise all of them
+    let goals: Vec<&Goal> = match command_helpers::get_string_option(command, "name") {
+        Ok(name) => match select_goal(user, Some(&name)) {
+            Ok(goal) => vec![goal],
+            Err(message) => {
+                let response = responses::default_response(&message);
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => user.active_goals().collect(),
+    };
+    let selected: HashSet<&str> = goals.iter().map(|goal| goal.name.as_str()).collect();
+
+    // Build the stats embed
+    let title = if is_self {
+        "📊 Your Stats"
+    } else {
+        "📊 User Stats"
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .color(0x00d4ff); // Light blue color
+
+    // Add user mention if not self
+    if !is_self {
+        embed = embed.description(format!("<@{}>", target_user_id));
+    }
+
+    let today = data_read.user_today(&guild_id, &target_user_id);
+    if let [goal] = goals.as_slice() {
+        // Goal field
+        embed = embed.field("🎯 Goal", &goal.description, false);
+
+        // Streak fields
+        let unit = goal.frequency.streak_unit();
+        embed = embed
+            .field("🔥 Current Streak", format!("{} {}", goal.current_streak, unit), true)
+            .field("🏆 Longest Streak", format!("{} {}", goal.longest_streak, unit), true);
+        if let GoalFrequency::Weekly { times } = goal.frequency {
+            embed = embed.field("📆 This Week", format!("{}/{} check-ins", goal.checkins_in_week_of(today), times), true);
+        }
+    } else {
+        // A field per goal
+        for goal in &goals {
+            let unit = goal.frequency.streak_unit();
+            let mut summary = format!("{}\n🔥 {} {} · 🏆 {} {}", goal.description, goal.current_streak, unit, goal.longest_streak, unit);
+            if let GoalFrequency::Weekly { times } = goal.frequency {
+                summary.push_str(&format!(" · 📆 {}/{} this week", goal.checkins_in_week_of(today), times));
+            }
+            embed = embed.field(format!("🎯 {}", goal.name), summary, false);
+        }
+    }
+
+    // Check-in history field; a check-in covering several goals counts once
+    let checkin_days = |records: Vec<&crate::data::CheckinRecord>| {
+        records
+            .into_iter()
+            .filter(|record| record.goal.as_deref().map(|goal| selected.contains(goal)).unwrap_or(true))
+            .map(|record| record.checkin_date)
+            .collect::<HashSet<_>>()
+            .len()
+    };
+    let total_checkins = checkin_days(data_read.get_user_checkins(&guild_id, &target_user_id));
+    let recent_checkins = checkin_days(
+        data_read.get_user_checkins_in_range(&guild_id, &target_user_id, today - Duration::days(6), today),
+    );
+    embed = embed.field(
+        "📈 Check-ins",
+        format!("{} total, {} in the last 7 days", total_checkins, recent_checkins),
+        false,
+    );
+
+    // Optional calendar of this month, per goal
+    let show_calendar = command_helpers::get_boolean_option(command, "calendar").unwrap_or(false);
+    if show_calendar {
+        let month_start = today.with_day(1).unwrap_or(today);
+        for goal in &goals {
+            let goal_history = history::for_goal(&data_read, &guild_id, user, goal, today);
+            let title = if goals.len() > 1 {
+                format!("🗓️ {} · {}", month_start.format("%B %Y"), goal.name)
+            } else {
+                format!("🗓️ {}", month_start.format("%B %Y"))
+            };
+            embed = embed.field(title, goal_history.render_month(month_start), false);
+        }
+    }
+
+    // Break field, for a pause in progress or coming up
+    if let Some(pause) = user.pauses.iter().find(|pause| pause.to >= today) {
+        let pause_status = if pause.from <= today {
+            format!("On a break until {}", pause.to)
+        } else {
+            format!("Break scheduled from {} to {}", pause.from, pause.to)
+        };
+        embed = embed.field("⏸️ Break", pause_status, false);
+    }
+
+    // Rest day schedule, leaving out dates already past
+    let upcoming_rest_days = RestDays {
+        weekdays: user.rest_days.weekdays.clone(),
+        dates: user.rest_days.dates.iter().copied().filter(|date| *date >= today).collect(),
+    };
+    if !upcoming_rest_days.is_empty() {
+        embed = embed.field("💤 Rest Days", upcoming_rest_days.describe(), false);
+    }
+
+    if let Some(timezone) = &user.timezone {
+        embed = embed.field("🌐 Timezone", timezone, false);
+    }
 
     // Check-in status field
     let checkin_status = if let Some(daily_post) = data_read.daily_posts.get(&guild_id) {
-        let post_date = daily_post.posted_at.date_naive();
-        let now = Utc::now();
+        // The day a check-in now would count for: today in the member's timezone, or the day a late post stands in for
+        let checkin_date = daily_post.catch_up_for.unwrap_or(today);
+        let now = data_read.now();
 
-        // Check if user has checked in today
-        let has_checked_in_today = user.last_checkin_date
-            .map(|last_checkin| last_checkin >= post_date)
-            .unwrap_or(false);
+        // Goals still waiting for today's check-in; weekly goals never are
+        let checked_in = |goal: &&Goal| goal.last_checkin_date.map(|last_checkin| last_checkin >= checkin_date).unwrap_or(false);
+        let pending: Vec<&str> = goals
+            .iter()
+            .filter(|goal| goal.frequency == GoalFrequency::Daily && !checked_in(goal))
+            .map(|goal| goal.name.as_str())
+            .collect();
 
-        if has_checked_in_today {
+        if pending.is_empty() && !goals.iter().any(checked_in) {
+            "➖ Optional - weekly goals only need their weekly check-ins".to_string()
+        } else if pending.is_empty() {
             "✅ Complete".to_string()
         } else {
             // Calculate time remaining
-            let deadline = daily_post.posted_at + Duration::hours(24);
+            let deadline = daily_post.response_deadline();
             let time_remaining = deadline.signed_duration_since(now);
+            let pending_goals = if goals.len() > 1 {
+                format!(" ({})", pending.join(", "))
+            } else {
+                String::new()
+            };
 
             if time_remaining.num_seconds() > 0 {
                 let deadline_unix = deadline.timestamp();
-                format!("⏳ Not yet complete\n**Streak expires:** <t:{}:R>", deadline_unix)
+                format!("⏳ Not yet complete{}\n**Streak expires:** <t:{}:R>", pending_goals, deadline_unix)
             } else {
-                "❌ Missed (deadline passed)".to_string()
+                format!("❌ Missed (deadline passed){}", pending_goals)
             }
         }
     } else {
@@ -288,3 +1089,327 @@ pub async fn stats(
     info!("Successfully displayed stats for user {} in guild {}", target_user_id, guild_id);
     Ok(())
 }
+
+pub async fn freezes(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let user_id = command_helpers::get_user_id(command);
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    info!("Freezes command executed by user {}", user_id);
+
+    let data_read = data.read().await;
+
+    let user = match data_read.get_user(&guild_id, &user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let response = responses::default_response("You're not currently registered for daily check-ins. Use `/register-goal` to get started!");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+    let policy = data_read
+        .get_server_config(&guild_id)
+        .map(|config| config.streak_policy.clone())
+        .unwrap_or_default();
+
+    let goals: Vec<&Goal> = match command_helpers::get_string_option(command, "name") {
+        Ok(name) => match select_goal(user, Some(&name)) {
+            Ok(goal) => vec![goal],
+            Err(message) => {
+                let response = responses::default_response(&message);
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => user.active_goals().collect(),
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title("🧊 Streak Freezes")
+        .color(0x00d4ff); // Light blue color
+
+    let next = |goal: &Goal| if goal.frequency != GoalFrequency::Daily {
+        "Freezes are earned by daily goals".to_string()
+    } else if policy.freeze_every_days == 0 {
+        "Freezes are disabled on this server".to_string()
+    } else if goal.freeze_tokens >= policy.freeze_max_tokens {
+        "You're holding the maximum - use one before earning more".to_string()
+    } else {
+        let next_milestone = (goal.current_streak / policy.freeze_every_days + 1) * policy.freeze_every_days;
+        format!("At a streak of {} {}", next_milestone, goal.frequency.streak_unit())
+    };
+
+    // Balances are kept per goal
+    if let [goal] = goals.as_slice() {
+        embed = embed
+            .field("Balance", format!("{} / {}", goal.freeze_tokens, policy.freeze_max_tokens), true)
+            .field("Next Freeze", next(goal), true);
+    } else {
+        for goal in &goals {
+            embed = embed.field(
+                format!("🎯 {}", goal.name),
+                format!("Balance: {} / {}\nNext: {}", goal.freeze_tokens, policy.freeze_max_tokens, next(goal)),
+                true,
+            );
+        }
+    }
+
+    let mut events: Vec<_> = goals
+        .iter()
+        .flat_map(|goal| goal.freeze_history.iter().map(move |event| (goal, event)))
+        .collect();
+    events.sort_by_key(|(_, event)| std::cmp::Reverse(event.date));
+    let history = events
+        .into_iter()
+        .take(10)
+        .map(|(goal, event)| {
+            let goal_label = if goals.len() > 1 { format!(" ({})", goal.name) } else { String::new() };
+            match event.kind {
+                FreezeEventKind::Earned => format!("➕ {} - earned at a streak of {} {}{}", event.date, event.streak, goal.frequency.streak_unit(), goal_label),
+                FreezeEventKind::Used => format!("➖ {} - covered a missed check-in{}", event.date, goal_label),
+            }
+        })
+        .collect::<Vec<_>>();
+    let history = if history.is_empty() {
+        "No freezes earned yet".to_string()
+    } else {
+        history.join("\n")
+    };
+    embed = embed.field("Recent History", history, false);
+
+    embed = embed.footer(CreateEmbedFooter::new(
+        "A freeze is used automatically when you miss a check-in the grace period doesn't cover",
+    ));
+
+    let response = responses::embed_response(embed);
+    command.create_response(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+pub async fn history(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    // Check if a user parameter was provided, otherwise use the command user
+    let (target_user_id, is_self) = target_user(command);
+
+    info!("History command executed by user {} for user {}", command_helpers::get_user_id(command), target_user_id);
+
+    let data_read = data.read().await;
+    let today = data_read.user_today(&guild_id, &target_user_id);
+
+    // The last month shown, and how many to show before it
+    let last_month = match command_helpers::get_string_option(command, "month") {
+        Ok(month) => match NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d") {
+            Ok(month_start) => month_start,
+            Err(_) => {
+                let response = responses::default_response("Invalid month. Use YYYY-MM format (e.g., '2025-11').");
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+        Err(_) => today.with_day(1).unwrap_or(today),
+    };
+    let months = command_helpers::get_integer_option(command, "months").unwrap_or(1).clamp(1, 3) as u32;
+
+    let user = match data_read.get_user(&guild_id, &target_user_id) {
+        Some(user) if user.is_active => user,
+        _ => {
+            let msg = if is_self {
+                "You're not currently registered for daily check-ins. Use `/register-goal` to get started!"
+            } else {
+                "That user is not currently registered for daily check-ins."
+            };
+            let response = responses::default_response(msg);
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let goal = match select_goal(user, command_helpers::get_string_option(command, "name").ok().as_deref()) {
+        Ok(goal) => goal,
+        Err(message) => {
+            let response = responses::default_response(&message);
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let embed = history_embed(&data_read, &guild_id, user, goal, today, last_month, months, is_self);
+    let response = responses::embed_response(embed);
+    command.create_response(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Calendars for `months` months ending with `last_month`, plus completion rates
+#[allow(clippy::too_many_arguments)]
+fn history_embed(
+    data: &BotData,
+    guild_id: &str,
+    user: &UserData,
+    goal: &Goal,
+    today: NaiveDate,
+    last_month: NaiveDate,
+    months: u32,
+    is_self: bool,
+) -> CreateEmbed {
+    let user_history = history::for_goal(data, guild_id, user, goal, today);
+
+    let mut embed = CreateEmbed::new()
+        .title(if is_self { "🗓️ Your Check-in History" } else { "🗓️ Check-in History" })
+        .color(0x00d4ff); // Light blue color
+
+    let goal_line = format!("🎯 {} (`{}`)", goal.description, goal.name);
+    embed = if is_self {
+        embed.description(goal_line)
+    } else {
+        embed.description(format!("<@{}>\n{}", user.user_id, goal_line))
+    };
+
+    for back in (0..months).rev() {
+        let Some(month_start) = last_month.checked_sub_months(Months::new(back)) else {
+            continue;
+        };
+        embed = embed.field(
+            month_start.format("%B %Y").to_string(),
+            user_history.render_month(month_start),
+            false,
+        );
+    }
+
+    embed = embed
+        .field("Last 30 days", history::format_completion(user_history.completion(30)), true)
+        .field("Last 90 days", history::format_completion(user_history.completion(90)), true)
+        .field("Last 365 days", history::format_completion(user_history.completion(365)), true)
+        .footer(CreateEmbedFooter::new(history::LEGEND));
+
+    embed
+}
+
+pub async fn heatmap(
+    ctx: &Context,
+    command: &CommandInteraction,
+    data: SharedBotData,
+) -> serenity::Result<()> {
+    let guild_id = command_helpers::get_guild_id(command)?;
+
+    // Check if a user parameter was provided, otherwise use the command user
+    let (target_user_id, is_self) = target_user(command);
+
+    info!("Heatmap command executed by user {} for user {}", command_helpers::get_user_id(command), target_user_id);
+
+    let (image, summary) = {
+        let data_read = data.read().await;
+
+        let user = match data_read.get_user(&guild_id, &target_user_id) {
+            Some(user) if user.is_active => user,
+            _ => {
+                let msg = if is_self {
+                    "You're not currently registered for daily check-ins. Use `/register-goal` to get started!"
+                } else {
+                    "That user is not currently registered for daily check-ins."
+                };
+                let response = responses::default_response(msg);
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        };
+
+        let goal = match select_goal(user, command_helpers::get_string_option(command, "name").ok().as_deref()) {
+            Ok(goal) => goal,
+            Err(message) => {
+                let response = responses::default_response(&message);
+                command.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+        };
+
+        let today = data_read.user_today(&guild_id, &target_user_id);
+        let user_history = history::for_goal(&data_read, &guild_id, user, goal, today);
+        let image = heatmap::render(today, |date| user_history.status(date));
+        let summary = format!(
+            "<@{}> `{}` - {} check-ins in the last year, {} of expected days",
+            user.user_id,
+            goal.name,
+            data_read
+                .get_user_checkins_in_range(&guild_id, &user.user_id, today - Duration::days(364), today)
+                .iter()
+                .filter(|record| record.goal.as_deref() == Some(goal.name.as_str()))
+                .count(),
+            history::format_completion(user_history.completion(365)),
+        );
+        (image, summary)
+    };
+
+    let image = match image {
+        Ok(image) => image,
+        Err(e) => {
+            error!("Failed to render heatmap: {}", e);
+            let response = responses::default_response("Failed to render the heatmap. Please try again.");
+            command.create_response(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let embed = CreateEmbed::new()
+        .title(if is_self { "🟩 Your Check-in Heatmap" } else { "🟩 Check-in Heatmap" })
+        .description(summary)
+        .image("attachment://heatmap.png")
+        .footer(CreateEmbedFooter::new("Green: checked in · Light green: grace · Blue: freeze · Grey: paused or rest day · Red: missed · Yellow: today"))
+        .color(0x30a14e); // Heatmap green
+
+    let message = CreateInteractionResponseMessage::new()
+        .add_file(CreateAttachment::bytes(image, "heatmap.png"))
+        .embed(embed);
+    command.create_response(&ctx.http, CreateInteractionResponse::Message(message)).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    const GUILD: &str = "1000";
+    const MEMBER: &str = "2000";
+
+    async fn add(data: &SharedBotData, description: &str, name: Option<&str>) -> GoalRegistration {
+        add_goal(data, GUILD, MEMBER, description.to_string(), name.map(str::to_string), GoalFrequency::Daily)
+            .await
+            .unwrap()
+    }
+
+    fn active_goals(data: &BotData) -> usize {
+        data.get_user(GUILD, MEMBER).unwrap().active_goals().count()
+    }
+
+    #[tokio::test]
+    async fn goals_beyond_the_limit_are_rejected() {
+        let data: SharedBotData = Arc::new(RwLock::new(BotData::default()));
+        for i in 0..MAX_GOALS {
+            let registration = add(&data, &format!("goal{} every day", i), None).await;
+            assert!(matches!(registration, GoalRegistration::Saved(_)));
+        }
+
+        assert_eq!(add(&data, "One more goal", None).await, GoalRegistration::TooManyGoals);
+        assert_eq!(active_goals(&*data.read().await), MAX_GOALS);
+
+        // Existing goals can still be updated
+        assert!(matches!(add(&data, "Goal zero, reworded", Some("goal0")).await, GoalRegistration::Saved(_)));
+
+        // Removing a goal makes room again
+        data.write().await.get_user_mut(GUILD, MEMBER).unwrap().goal_mut("goal1").unwrap().is_active = false;
+        assert!(matches!(add(&data, "One more goal", None).await, GoalRegistration::Saved(_)));
+        assert_eq!(active_goals(&*data.read().await), MAX_GOALS);
+    }
+}
@@ -1,40 +1,480 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use chrono::{DateTime, Utc, NaiveDate};
+use chrono::{DateTime, Datelike, Duration, Utc, NaiveDate, Weekday};
+use chrono_tz::Tz;
 use anyhow::Result;
-use tokio::fs;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use crate::{clock::{self, Clock}, migrations::{self, CURRENT_SCHEMA_VERSION}, storage::{PendingChanges, Storage}};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserData {
     pub user_id: String,
-    pub goal: String,
+    pub goals: Vec<Goal>,
+    #[serde(default)]
+    pub pauses: Vec<PausePeriod>,
+    #[serde(default)]
+    pub rest_days: RestDays,
+    #[serde(default)]
+    pub timezone: Option<String>, // IANA name; None follows the server's timezone
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A habit a user checks in on, with its own streak
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub name: String, // short identifier for goal selectors and `#name` check-in tags
+    pub description: String,
     pub current_streak: u32,
     pub longest_streak: u32,
     pub last_checkin_date: Option<NaiveDate>,
     pub grace_period_start: Option<NaiveDate>,
+    #[serde(default)]
+    pub grace_days: Vec<NaiveDate>, // missed days excused by the grace period, pruned to the policy window
+    #[serde(default)]
+    pub freeze_tokens: u32,
+    #[serde(default)]
+    pub freeze_history: Vec<FreezeEvent>, // oldest first, capped at MAX_FREEZE_HISTORY
+    #[serde(default)]
+    pub frequency: GoalFrequency,
+    #[serde(default)]
+    pub week_checkins: u32, // weekly goals: check-ins in the week of last_checkin_date
+    #[serde(default)]
+    pub last_met_week: Option<NaiveDate>, // weekly goals: Monday of the last week that met the target
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// How often a goal expects check-ins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum GoalFrequency {
+    #[default]
+    Daily, // the streak counts consecutive check-in days
+    Weekly { times: u32 }, // the streak counts consecutive weeks with at least `times` check-ins
+}
+
+impl GoalFrequency {
+    pub fn describe(self) -> String {
+        match self {
+            Self::Daily => "Daily".to_string(),
+            Self::Weekly { times } => format!("{}x a week", times),
+        }
+    }
+
+    /// What the streak counts
+    pub fn streak_unit(self) -> &'static str {
+        match self {
+            Self::Daily => "days",
+            Self::Weekly { .. } => "weeks",
+        }
+    }
+}
+
+/// The Monday starting the week `date` falls in
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Longest goal name users can pick
+pub const MAX_GOAL_NAME_LENGTH: usize = 20;
+
+/// Most active goals a user can have; `/stats` shows up to two embed fields per goal
+/// next to its own five, within Discord's limit of 25
+pub const MAX_GOALS: usize = 10;
+
+/// Derive a goal name from its description: the first word, lowercased ("Exercise daily" -> "exercise")
+pub fn goal_slug(description: &str) -> String {
+    let slug: String = description
+        .split(|c: char| !c.is_alphanumeric())
+        .find(|word| !word.is_empty())
+        .unwrap_or("goal")
+        .to_lowercase()
+        .chars()
+        .take(MAX_GOAL_NAME_LENGTH)
+        .collect();
+    slug
+}
+
+/// How many freeze events are kept per goal
+pub const MAX_FREEZE_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreezeEventKind {
+    Earned,
+    Used,
+}
+
+/// A streak freeze token being earned at a milestone or spent on a missed day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreezeEvent {
+    pub kind: FreezeEventKind,
+    pub date: NaiveDate, // the day it was earned, or the missed day it covered
+    pub streak: u32, // streak at the time
+}
+
+/// Days a user is away: no pings, and missed check-ins don't count against the streak
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PausePeriod {
+    pub from: NaiveDate,
+    pub to: NaiveDate, // inclusive
+}
+
+impl PausePeriod {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from <= date && date <= self.to
+    }
+}
+
+/// Days a user deliberately skips, e.g. weekends: no pings, and they never break a streak
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestDays {
+    pub weekdays: Vec<Weekday>, // Monday first
+    pub dates: Vec<NaiveDate>, // sorted
+}
+
+/// Most one-off rest dates a user can have scheduled
+pub const MAX_REST_DATES: usize = 30;
+
+impl RestDays {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.weekdays.contains(&date.weekday()) || self.dates.binary_search(&date).is_ok()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weekdays.is_empty() && self.dates.is_empty()
+    }
+
+    /// Like "Sat, Sun · 2025-12-24", or "None"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.weekdays.is_empty() {
+            parts.push(self.weekdays.iter().map(Weekday::to_string).collect::<Vec<_>>().join(", "));
+        }
+        if !self.dates.is_empty() {
+            parts.push(self.dates.iter().map(NaiveDate::to_string).collect::<Vec<_>>().join(", "));
+        }
+        if parts.is_empty() {
+            "None".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
+impl UserData {
+    pub fn new(user_id: String, now: DateTime<Utc>) -> Self {
+        Self {
+            user_id,
+            goals: Vec::new(),
+            pauses: Vec::new(),
+            rest_days: RestDays::default(),
+            timezone: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The timezone the user's days are counted in: their own, or else the server's
+    pub fn timezone_or(&self, server_timezone: Tz) -> Tz {
+        self.timezone
+            .as_deref()
+            .and_then(|timezone| timezone.parse().ok())
+            .unwrap_or(server_timezone)
+    }
+
+    /// Whether the user paused check-ins for this day
+    pub fn is_paused_on(&self, date: NaiveDate) -> bool {
+        self.pauses.iter().any(|pause| pause.contains(date))
+    }
+
+    /// Whether the user is on a break or resting this day
+    pub fn is_away_on(&self, date: NaiveDate) -> bool {
+        self.is_paused_on(date) || self.rest_days.contains(date)
+    }
+
+    /// `is_away_on` as an owned predicate, for use while the user's goals are borrowed mutably
+    pub fn away_filter(&self) -> impl Fn(NaiveDate) -> bool {
+        let pauses = self.pauses.clone();
+        let rest_days = self.rest_days.clone();
+        move |date| pauses.iter().any(|pause| pause.contains(date)) || rest_days.contains(date)
+    }
+
+    pub fn goal(&self, name: &str) -> Option<&Goal> {
+        self.goals.iter().find(|goal| goal.name == name)
+    }
+
+    pub fn goal_mut(&mut self, name: &str) -> Option<&mut Goal> {
+        self.goals.iter_mut().find(|goal| goal.name == name)
+    }
+
+    pub fn active_goals(&self) -> impl Iterator<Item = &Goal> {
+        self.goals.iter().filter(|goal| goal.is_active)
+    }
+
+    /// `base`, or `base-2`, `base-3`, ... if the user already has a goal by that name
+    pub fn unused_goal_name(&self, base: &str) -> String {
+        if self.goal(base).is_none() {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{}-{}", base, n))
+            .find(|name| self.goal(name).is_none())
+            .unwrap_or_else(|| base.to_string())
+    }
+}
+
+impl Goal {
+    pub fn new(name: String, description: String, now: DateTime<Utc>) -> Self {
+        Self {
+            name,
+            description,
+            current_streak: 0,
+            longest_streak: 0,
+            last_checkin_date: None,
+            grace_period_start: None,
+            grace_days: Vec::new(),
+            freeze_tokens: 0,
+            freeze_history: Vec::new(),
+            frequency: GoalFrequency::Daily,
+            week_checkins: 0,
+            last_met_week: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Weekly goals: check-ins so far in the week containing `today`
+    pub fn checkins_in_week_of(&self, today: NaiveDate) -> u32 {
+        match self.last_checkin_date {
+            Some(last) if week_start(last) == week_start(today) => self.week_checkins,
+            _ => 0,
+        }
+    }
+
+    /// Switch to another frequency; streaks in different units don't carry over
+    pub fn set_frequency(&mut self, frequency: GoalFrequency) {
+        if self.frequency == frequency {
+            return;
+        }
+        self.frequency = frequency;
+        self.current_streak = 0;
+        self.grace_period_start = None;
+        self.grace_days.clear();
+        self.week_checkins = 0;
+        self.last_met_week = None;
+    }
+
+    /// Bring back a removed goal: it keeps its name and longest streak, but starts over
+    pub fn reactivate(&mut self, description: String, frequency: GoalFrequency, now: DateTime<Utc>) {
+        self.description = description;
+        self.current_streak = 0;
+        self.last_checkin_date = None;
+        self.grace_period_start = None;
+        self.grace_days.clear();
+        self.set_frequency(frequency);
+        self.is_active = true;
+        self.updated_at = now;
+    }
+
+    /// The streak state a check-in is about to change, so it can be put back if the check-in is revoked
+    pub fn snapshot(&self) -> StreakSnapshot {
+        StreakSnapshot {
+            current_streak: self.current_streak,
+            longest_streak: self.longest_streak,
+            last_checkin_date: self.last_checkin_date,
+            grace_period_start: self.grace_period_start,
+            grace_days: self.grace_days.clone(),
+            freeze_tokens: self.freeze_tokens,
+            week_checkins: self.week_checkins,
+            last_met_week: self.last_met_week,
+        }
+    }
+
+    /// Put back the streak state from before a check-in. Freeze events after the last
+    /// check-in it remembers are dropped, since they came from that check-in or later.
+    pub fn restore(&mut self, snapshot: &StreakSnapshot) {
+        self.current_streak = snapshot.current_streak;
+        self.longest_streak = snapshot.longest_streak;
+        self.last_checkin_date = snapshot.last_checkin_date;
+        self.grace_period_start = snapshot.grace_period_start;
+        self.grace_days = snapshot.grace_days.clone();
+        self.freeze_tokens = snapshot.freeze_tokens;
+        self.week_checkins = snapshot.week_checkins;
+        self.last_met_week = snapshot.last_met_week;
+        self.freeze_history
+            .retain(|event| Some(event.date) <= snapshot.last_checkin_date);
+    }
+
+    /// Whether a freeze token was spent to cover this day
+    pub fn is_frozen(&self, date: NaiveDate) -> bool {
+        self.freeze_history
+            .iter()
+            .any(|event| event.kind == FreezeEventKind::Used && event.date == date)
+    }
+
+    pub fn record_freeze_event(&mut self, kind: FreezeEventKind, date: NaiveDate) {
+        self.freeze_history.push(FreezeEvent { kind, date, streak: self.current_streak });
+        if self.freeze_history.len() > MAX_FREEZE_HISTORY {
+            let excess = self.freeze_history.len() - MAX_FREEZE_HISTORY;
+            self.freeze_history.drain(..excess);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub guild_id: String,
     pub checkin_channel_id: Option<String>,
     pub timezone: String,
     pub daily_time: String,
+    #[serde(default)]
+    pub cron_schedule: Option<String>, // overrides daily_time when set
+    #[serde(default)]
+    pub streak_policy: StreakPolicy,
+    #[serde(default)]
+    pub checkin_requirements: CheckinRequirements,
+    #[serde(default)]
+    pub checkin_feedback: CheckinFeedback,
+    #[serde(default)]
+    pub keep_deleted_checkins: bool, // whether a check-in still counts after its message is deleted
+    #[serde(default)]
+    pub manager_role_ids: Vec<String>, // roles that may run the admin commands besides administrators
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl ServerConfig {
+    /// A configuration with the defaults used before an admin changes anything
+    pub fn new(guild_id: String, now: DateTime<Utc>) -> Self {
+        Self {
+            guild_id,
+            checkin_channel_id: None,
+            timezone: "UTC".to_string(),
+            daily_time: "09:00".to_string(),
+            cron_schedule: None,
+            streak_policy: StreakPolicy::default(),
+            checkin_requirements: CheckinRequirements::default(),
+            checkin_feedback: CheckinFeedback::default(),
+            keep_deleted_checkins: false,
+            manager_role_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// What a check-in must contain to count; the defaults accept anything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckinRequirements {
+    pub min_length: u32, // characters; 0 accepts any length
+    pub require_attachment: bool, // e.g. a photo as proof
+    pub keyword: Option<String>, // a word or hashtag the check-in must mention, ignoring case
+}
+
+impl CheckinRequirements {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Why a check-in with this text and number of attachments doesn't count; empty if it does
+    pub fn problems(&self, content: &str, attachments: usize) -> Vec<String> {
+        let mut problems = Vec::new();
+        let length = content.trim().chars().count();
+        if length < self.min_length as usize {
+            problems.push(format!("it needs at least {} characters (it has {})", self.min_length, length));
+        }
+        if self.require_attachment && attachments == 0 {
+            problems.push("it needs an attachment, like a photo of your progress".to_string());
+        }
+        if let Some(keyword) = &self.keyword {
+            if !content.to_lowercase().contains(&keyword.to_lowercase()) {
+                problems.push(format!("it needs to mention `{}`", keyword));
+            }
+        }
+        problems
+    }
+}
+
+/// How the bot acknowledges a thread reply that counted as a check-in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckinFeedback {
+    pub emoji: Option<String>, // reaction on every counted reply; None for no reaction
+    pub milestone_emoji: Option<String>, // reaction instead when a streak reaches a milestone
+    pub reply_with_streak: bool, // also reply with the new streak counts
+}
+
+impl Default for CheckinFeedback {
+    fn default() -> Self {
+        Self {
+            emoji: Some("✅".to_string()),
+            milestone_emoji: Some("🎉".to_string()),
+            reply_with_streak: false,
+        }
+    }
+}
+
+/// How forgiving a server is about missed check-in days
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreakPolicy {
+    pub grace_min_streak: u32, // streak needed before missed days can be excused
+    pub grace_max_consecutive_misses: u32, // 0 disables the grace period
+    pub grace_max_days_per_window: u32,
+    pub grace_window_days: u32,
+    pub grace_days_count_toward_streak: bool,
+    pub freeze_every_days: u32, // streak milestone interval that earns a freeze token; 0 disables freezes
+    pub freeze_max_tokens: u32,
+}
+
+impl Default for StreakPolicy {
+    fn default() -> Self {
+        Self {
+            grace_min_streak: 30,
+            grace_max_consecutive_misses: 2,
+            grace_max_days_per_window: 2,
+            grace_window_days: 30,
+            grace_days_count_toward_streak: false,
+            freeze_every_days: 7,
+            freeze_max_tokens: 2,
+        }
+    }
+}
+
+/// A goal's streak state from just before a check-in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreakSnapshot {
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub last_checkin_date: Option<NaiveDate>,
+    pub grace_period_start: Option<NaiveDate>,
+    pub grace_days: Vec<NaiveDate>,
+    pub freeze_tokens: u32,
+    pub week_checkins: u32,
+    pub last_met_week: Option<NaiveDate>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckinRecord {
     pub user_id: String,
+    #[serde(default)]
+    pub goal: Option<String>, // the goal checked in on; None only for records from before goals had names
     pub checkin_date: NaiveDate,
     pub message_id: Option<String>,
     pub thread_id: Option<String>,
     pub daily_post_id: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<StreakSnapshot>, // the goal's streak before this check-in; None on records from before check-ins could be revoked
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,41 +485,105 @@ pub struct DailyPost {
     pub thread_id: Option<String>,
     pub posted_at: DateTime<Utc>, // When the post was actually created
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub catch_up_for: Option<NaiveDate>, // Set on late posts made up after bot downtime
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>, // Overrides the usual 24-hour response window
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+impl DailyPost {
+    /// When responses to this post stop counting
+    pub fn response_deadline(&self) -> DateTime<Utc> {
+        self.deadline.unwrap_or(self.posted_at + Duration::hours(24))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BotData {
+    pub schema_version: u32,
     pub servers: HashMap<String, ServerConfig>,
     pub users: HashMap<String, HashMap<String, UserData>>, // guild_id -> user_id -> UserData
     pub daily_posts: HashMap<String, DailyPost>, // guild_id -> current post
+    pub checkins: HashMap<String, Vec<CheckinRecord>>, // guild_id -> check-in history
+    pub missed_post_days: HashMap<String, Vec<NaiveDate>>, // guild_id -> days the bot failed to post
+    #[serde(skip)]
+    storage: Option<Arc<dyn Storage>>,
+    #[serde(skip)]
+    changes: PendingChanges,
+    #[serde(skip)]
+    config_changed: Arc<Notify>,
+    #[serde(skip, default = "clock::system")]
+    clock: Arc<dyn Clock>,
 }
 
-impl BotData {
-    fn data_file_path() -> String {
-        std::env::var("DATA_FILE_PATH").unwrap_or_else(|_| "bot_data.json".to_string())
+impl Default for BotData {
+    fn default() -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            servers: HashMap::new(),
+            users: HashMap::new(),
+            daily_posts: HashMap::new(),
+            checkins: HashMap::new(),
+            missed_post_days: HashMap::new(),
+            storage: None,
+            changes: PendingChanges::default(),
+            config_changed: Arc::new(Notify::new()),
+            clock: clock::system(),
+        }
     }
+}
 
-    pub async fn load() -> Result<Self> {
-        let file_path = Self::data_file_path();
-        match fs::read_to_string(&file_path).await {
-            Ok(content) => {
-                let data: BotData = serde_json::from_str(&content)?;
-                Ok(data)
-            }
-            Err(_) => {
-                // File doesn't exist, return default
-                Ok(BotData::default())
-            }
+impl BotData {
+    /// Load the data set from `storage` and keep using it for saves.
+    /// Documents upgraded from an older schema version are written back right away.
+    pub async fn load(storage: Arc<dyn Storage>) -> Result<Self> {
+        let mut data = storage.load().await?;
+        data.storage = Some(storage);
+        if data.changes.everything {
+            data.save().await?;
         }
+        Ok(data)
     }
 
-    pub async fn save(&self) -> Result<()> {
-        let file_path = Self::data_file_path();
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&file_path, content).await?;
+    /// Deserialize a persisted document, migrating it to the current schema version first
+    pub fn from_document(document: serde_json::Value) -> Result<Self> {
+        let (document, migrated) = migrations::migrate(document)?;
+        let mut data: BotData = serde_json::from_value(document)?;
+        data.changes.everything = migrated;
+        Ok(data)
+    }
+
+    /// Persist everything changed since the last save.
+    /// Data without an attached storage backend lives in memory only.
+    pub async fn save(&mut self) -> Result<()> {
+        if let Some(storage) = self.storage.clone() {
+            storage.save(self, &self.changes).await?;
+        }
+        self.changes = PendingChanges::default();
         Ok(())
     }
 
+    /// Signalled whenever a server configuration is added or updated
+    pub fn config_changed(&self) -> Arc<Notify> {
+        self.config_changed.clone()
+    }
+
+    /// The current time, according to the bot's clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// The bot's clock, for waiting on it
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    /// Replace the bot's clock, e.g. with a fake one in tests
+    #[cfg(test)]
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
     pub fn get_user(&self, guild_id: &str, user_id: &str) -> Option<&UserData> {
         self.users.get(guild_id)?.get(user_id)
     }
@@ -89,19 +593,134 @@ impl BotData {
     }
 
     pub fn add_or_update_user(&mut self, guild_id: String, user_data: UserData) {
+        self.changes.users.insert((guild_id.clone(), user_data.user_id.clone()));
         self.users
             .entry(guild_id)
-            .or_insert_with(HashMap::new)
+            .or_default()
             .insert(user_data.user_id.clone(), user_data);
     }
 
     pub fn add_or_update_server(&mut self, server_config: ServerConfig) {
+        self.changes.servers.insert(server_config.guild_id.clone());
+        self.config_changed.notify_one();
         self.servers.insert(server_config.guild_id.clone(), server_config);
     }
 
     pub fn get_user_mut(&mut self, guild_id: &str, user_id: &str) -> Option<&mut UserData> {
+        // Handing out a mutable reference counts as a change
+        if self.get_user(guild_id, user_id).is_some() {
+            self.changes.users.insert((guild_id.to_string(), user_id.to_string()));
+        }
         self.users
             .get_mut(guild_id)
             .and_then(|guild_users| guild_users.get_mut(user_id))
     }
+
+    /// Mutable access to every user in a guild, e.g. for streak maintenance
+    pub fn get_guild_users_mut(&mut self, guild_id: &str) -> Option<&mut HashMap<String, UserData>> {
+        let guild_users = self.users.get_mut(guild_id)?;
+        for user_id in guild_users.keys() {
+            self.changes.users.insert((guild_id.to_string(), user_id.clone()));
+        }
+        Some(guild_users)
+    }
+
+    /// Replace the current daily post for a guild
+    pub fn set_daily_post(&mut self, daily_post: DailyPost) {
+        self.changes.daily_posts.insert(daily_post.guild_id.clone());
+        self.daily_posts.insert(daily_post.guild_id.clone(), daily_post);
+    }
+
+    /// Record that the bot failed to post a scheduled check-in on `date`,
+    /// so the day is never held against anyone's streak
+    pub fn add_missed_post_day(&mut self, guild_id: String, date: NaiveDate) {
+        let days = self.missed_post_days.entry(guild_id.clone()).or_default();
+        if !days.contains(&date) {
+            days.push(date);
+            days.sort();
+            self.changes.missed_post_days.insert(guild_id);
+        }
+    }
+
+    /// Append a check-in to the guild's history log
+    pub fn add_checkin(&mut self, guild_id: String, record: CheckinRecord) {
+        if !self.changes.rewritten_checkins.contains(&guild_id) {
+            *self.changes.new_checkins.entry(guild_id.clone()).or_default() += 1;
+        }
+        self.checkins
+            .entry(guild_id)
+            .or_default()
+            .push(record);
+    }
+
+    /// A guild's check-in log for changes other than appending, e.g. revoking a check-in.
+    /// The whole log is written on the next save.
+    pub fn checkins_mut(&mut self, guild_id: &str) -> &mut Vec<CheckinRecord> {
+        self.changes.new_checkins.remove(guild_id);
+        self.changes.rewritten_checkins.insert(guild_id.to_string());
+        self.checkins.entry(guild_id.to_string()).or_default()
+    }
+
+    /// The guild's timezone (UTC if it has no valid configuration)
+    pub fn guild_timezone(&self, guild_id: &str) -> Tz {
+        self.get_server_config(guild_id)
+            .and_then(|config| config.timezone.parse().ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Today's date in the guild's timezone
+    pub fn guild_today(&self, guild_id: &str) -> NaiveDate {
+        self.now().with_timezone(&self.guild_timezone(guild_id)).date_naive()
+    }
+
+    /// The timezone a member's check-in days are counted in: their own, or else the guild's
+    pub fn user_timezone(&self, guild_id: &str, user_id: &str) -> Tz {
+        let guild_timezone = self.guild_timezone(guild_id);
+        self.get_user(guild_id, user_id)
+            .map(|user| user.timezone_or(guild_timezone))
+            .unwrap_or(guild_timezone)
+    }
+
+    /// Today's date for a member, in their timezone
+    pub fn user_today(&self, guild_id: &str, user_id: &str) -> NaiveDate {
+        self.now().with_timezone(&self.user_timezone(guild_id, user_id)).date_naive()
+    }
+
+    /// All recorded check-ins for a guild, oldest first
+    pub fn get_checkins(&self, guild_id: &str) -> &[CheckinRecord] {
+        self.checkins
+            .get(guild_id)
+            .map(|records| records.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// All recorded check-ins for a single user in a guild, oldest first
+    pub fn get_user_checkins(&self, guild_id: &str, user_id: &str) -> Vec<&CheckinRecord> {
+        self.get_checkins(guild_id)
+            .iter()
+            .filter(|record| record.user_id == user_id)
+            .collect()
+    }
+
+    /// Check-ins for a guild whose check-in date falls within `start..=end`
+    pub fn get_checkins_in_range(&self, guild_id: &str, start: NaiveDate, end: NaiveDate) -> Vec<&CheckinRecord> {
+        self.get_checkins(guild_id)
+            .iter()
+            .filter(|record| record.checkin_date >= start && record.checkin_date <= end)
+            .collect()
+    }
+
+    /// Check-ins for a single user whose check-in date falls within `start..=end`
+    pub fn get_user_checkins_in_range(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<&CheckinRecord> {
+        self.get_checkins_in_range(guild_id, start, end)
+            .into_iter()
+            .filter(|record| record.user_id == user_id)
+            .collect()
+    }
 }
\ No newline at end of file
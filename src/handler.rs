@@ -4,14 +4,18 @@ use serenity::{
         application::Interaction,
         gateway::Ready,
         channel::Message,
+        event::MessageUpdateEvent,
+        id::{ChannelId, GuildId, MessageId},
     },
     prelude::*,
 };
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
 use tracing::{info, error};
-use crate::{bot::SharedBotData, commands, scheduler::DailyScheduler, streaks::StreakManager};
+use crate::{bot::SharedBotData, commands, platform::{DiscordPlatform, IncomingMessage}, scheduler::DailyScheduler, streaks::StreakManager};
 
 pub struct Handler {
     pub data: SharedBotData,
+    pub scheduler_started: AtomicBool,
 }
 
 #[async_trait]
@@ -25,10 +29,14 @@ impl EventHandler for Handler {
             info!("Successfully registered slash commands");
         }
 
-        // Start the daily scheduler
+        // Start the daily scheduler (Ready fires again on reconnects, so only once)
+        if self.scheduler_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
         let scheduler = DailyScheduler::new(self.data.clone());
+        let platform = Arc::new(DiscordPlatform::new(&ctx));
         tokio::spawn(async move {
-            scheduler.start(ctx).await;
+            scheduler.start(platform).await;
         });
         info!("Daily scheduler started");
     }
@@ -42,8 +50,34 @@ impl EventHandler for Handler {
     async fn message(&self, ctx: Context, msg: Message) {
         // Process message for potential check-in responses
         let streak_manager = StreakManager::new(self.data.clone());
-        if let Err(why) = streak_manager.process_message(&ctx, &msg).await {
+        let platform = DiscordPlatform::new(&ctx);
+        if let Err(why) = streak_manager.process_message(&platform, &IncomingMessage::from(&msg)).await {
             error!("Error processing message for streaks: {}", why);
         }
     }
+
+    async fn message_update(&self, ctx: Context, _old: Option<Message>, _new: Option<Message>, event: MessageUpdateEvent) {
+        // Discord also sends updates for things like link previews; only text and attachments matter
+        let Some(guild_id) = event.guild_id else {
+            return;
+        };
+        if event.content.is_none() && event.attachments.is_none() {
+            return;
+        }
+        let streak_manager = StreakManager::new(self.data.clone());
+        let platform = DiscordPlatform::new(&ctx);
+        if let Err(why) = streak_manager.process_message_update(&platform, guild_id, event.channel_id, event.id).await {
+            error!("Error processing edited message for streaks: {}", why);
+        }
+    }
+
+    async fn message_delete(&self, _ctx: Context, _channel_id: ChannelId, deleted_message_id: MessageId, guild_id: Option<GuildId>) {
+        let Some(guild_id) = guild_id else {
+            return;
+        };
+        let streak_manager = StreakManager::new(self.data.clone());
+        if let Err(why) = streak_manager.process_message_delete(guild_id, deleted_message_id).await {
+            error!("Error processing deleted message for streaks: {}", why);
+        }
+    }
 }
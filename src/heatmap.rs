@@ -0,0 +1,124 @@
+use crate::history::DayStatus;
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Columns in the heatmap, one per week, so a full year fits
+pub const WEEKS: usize = 53;
+
+const CELL: usize = 11;
+const GAP: usize = 3;
+const MARGIN: usize = 10;
+
+pub const WIDTH: usize = MARGIN * 2 + WEEKS * CELL + (WEEKS - 1) * GAP;
+pub const HEIGHT: usize = MARGIN * 2 + 7 * CELL + 6 * GAP;
+
+type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [0xff, 0xff, 0xff];
+
+/// Cell colour for each day status, in the style of GitHub's contribution graph
+pub fn color(status: DayStatus) -> Rgb {
+    match status {
+        DayStatus::CheckedIn => [0x30, 0xa1, 0x4e],
+        DayStatus::Grace => [0x9b, 0xe9, 0xa8],
+        DayStatus::Frozen => [0x79, 0xc0, 0xff],
+        DayStatus::Paused | DayStatus::Rest => [0xd0, 0xd7, 0xde],
+        DayStatus::Missed => [0xff, 0xab, 0xa8],
+        DayStatus::Pending => [0xf6, 0xe0, 0x8a],
+        DayStatus::NotScheduled | DayStatus::Untracked => [0xeb, 0xed, 0xf0],
+    }
+}
+
+/// The date drawn in a given cell: columns are Monday-first weeks ending with the one containing `today`
+pub fn cell_date(today: NaiveDate, week: usize, weekday: usize) -> NaiveDate {
+    let last_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    last_monday - Duration::weeks((WEEKS - 1 - week) as i64) + Duration::days(weekday as i64)
+}
+
+/// Top-left pixel of a cell
+pub fn cell_origin(week: usize, weekday: usize) -> (usize, usize) {
+    (MARGIN + week * (CELL + GAP), MARGIN + weekday * (CELL + GAP))
+}
+
+/// Render the year up to `today` as a PNG, colouring each day by `status`
+pub fn render(today: NaiveDate, status: impl Fn(NaiveDate) -> DayStatus) -> Result<Vec<u8>> {
+    let mut pixels = vec![0u8; WIDTH * HEIGHT * 3];
+    for pixel in pixels.chunks_exact_mut(3) {
+        pixel.copy_from_slice(&BACKGROUND);
+    }
+
+    for week in 0..WEEKS {
+        for weekday in 0..7 {
+            let date = cell_date(today, week, weekday);
+            if date > today {
+                continue;
+            }
+            let (x, y) = cell_origin(week, weekday);
+            fill_rect(&mut pixels, x, y, CELL, CELL, color(status(date)));
+        }
+    }
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, WIDTH as u32, HEIGHT as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+    }
+    Ok(png)
+}
+
+fn fill_rect(pixels: &mut [u8], x: usize, y: usize, width: usize, height: usize, rgb: Rgb) {
+    for row in y..y + height {
+        let start = (row * WIDTH + x) * 3;
+        for pixel in pixels[start..start + width * 3].chunks_exact_mut(3) {
+            pixel.copy_from_slice(&rgb);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(png_bytes: &[u8]) -> (png::OutputInfo, Vec<u8>) {
+        let decoder = png::Decoder::new(png_bytes);
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        buffer.truncate(info.buffer_size());
+        (info, buffer)
+    }
+
+    fn pixel(pixels: &[u8], (x, y): (usize, usize)) -> Rgb {
+        let start = (y * WIDTH + x) * 3;
+        [pixels[start], pixels[start + 1], pixels[start + 2]]
+    }
+
+    #[test]
+    fn the_last_column_is_the_current_week() {
+        // 2025-11-19 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 11, 19).unwrap();
+        assert_eq!(cell_date(today, WEEKS - 1, 0), NaiveDate::from_ymd_opt(2025, 11, 17).unwrap());
+        assert_eq!(cell_date(today, WEEKS - 1, 2), today);
+        assert_eq!(cell_date(today, 0, 0), NaiveDate::from_ymd_opt(2024, 11, 18).unwrap());
+    }
+
+    #[test]
+    fn days_are_painted_by_status_and_the_future_is_left_blank() {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 19).unwrap();
+        let checked_in = NaiveDate::from_ymd_opt(2025, 11, 18).unwrap();
+        let png_bytes = render(today, |date| {
+            if date == checked_in { DayStatus::CheckedIn } else { DayStatus::Missed }
+        })
+        .unwrap();
+
+        let (info, pixels) = decode(&png_bytes);
+        assert_eq!((info.width as usize, info.height as usize), (WIDTH, HEIGHT));
+        assert_eq!(pixel(&pixels, cell_origin(WEEKS - 1, 1)), color(DayStatus::CheckedIn));
+        assert_eq!(pixel(&pixels, cell_origin(WEEKS - 1, 2)), color(DayStatus::Missed));
+        assert_eq!(pixel(&pixels, cell_origin(WEEKS - 1, 3)), BACKGROUND);
+        assert_eq!(pixel(&pixels, (0, 0)), BACKGROUND);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{data::{BotData, Goal, GoalFrequency, UserData}, streaks::StreakManager};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashSet;
+
+/// What happened on a single day of a user's check-in history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayStatus {
+    CheckedIn,
+    Grace,       // missed, but excused by the grace period
+    Frozen,      // missed, but covered by a freeze token
+    Paused,
+    Rest,        // one of the user's rest days
+    Missed,
+    NotScheduled, // no check-in was expected (schedule didn't fire, the bot failed to post, or a weekly goal)
+    Pending,     // today, not checked in yet
+    Untracked,   // before the user registered, or in the future
+}
+
+impl DayStatus {
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::CheckedIn => "✅",
+            Self::Grace => "🛟",
+            Self::Frozen => "🧊",
+            Self::Paused => "⏸️",
+            Self::Rest => "💤",
+            Self::Missed => "❌",
+            Self::NotScheduled => "➖",
+            Self::Pending => "⏳",
+            Self::Untracked => "⬜",
+        }
+    }
+}
+
+/// Explains the calendar symbols
+pub const LEGEND: &str = "✅ checked in · 🛟 grace · 🧊 freeze · ⏸️ paused · 💤 rest day · ❌ missed · ➖ no check-in · ⏳ today";
+
+/// The check-in history of one of a user's goals in a guild, derived from the persisted check-in records
+pub struct History<'a, F> {
+    user: &'a UserData,
+    goal: &'a Goal,
+    checkin_days: HashSet<NaiveDate>,
+    is_checkin_day: F,
+    today: NaiveDate,
+}
+
+/// Build the history of `user`'s `goal` in `guild_id`, as of `today` in the member's timezone
+pub fn for_goal<'a>(
+    data: &BotData,
+    guild_id: &str,
+    user: &'a UserData,
+    goal: &'a Goal,
+    today: NaiveDate,
+) -> History<'a, impl Fn(NaiveDate) -> bool> {
+    History {
+        user,
+        goal,
+        checkin_days: data
+            .get_user_checkins(guild_id, &user.user_id)
+            .iter()
+            .filter(|record| record.goal.as_deref() == Some(goal.name.as_str()))
+            .map(|record| record.checkin_date)
+            .collect(),
+        is_checkin_day: StreakManager::checkin_day_filter(data, guild_id),
+        today,
+    }
+}
+
+impl<F: Fn(NaiveDate) -> bool> History<'_, F> {
+    pub fn status(&self, date: NaiveDate) -> DayStatus {
+        if self.checkin_days.contains(&date) {
+            DayStatus::CheckedIn
+        } else if date > self.today || date < self.goal.created_at.date_naive() {
+            DayStatus::Untracked
+        } else if self.user.is_paused_on(date) {
+            DayStatus::Paused
+        } else if self.user.rest_days.contains(date) {
+            DayStatus::Rest
+        } else if !(self.is_checkin_day)(date) || self.goal.frequency != GoalFrequency::Daily {
+            // Weekly goals don't expect any particular day
+            DayStatus::NotScheduled
+        } else if self.goal.is_frozen(date) {
+            DayStatus::Frozen
+        } else if self.goal.grace_days.contains(&date) {
+            DayStatus::Grace
+        } else if date == self.today {
+            DayStatus::Pending
+        } else {
+            DayStatus::Missed
+        }
+    }
+
+    /// Check-ins made and check-ins expected over the last `days` days, including today.
+    /// Paused and unscheduled days aren't expected; today only counts once checked in.
+    pub fn completion(&self, days: i64) -> (u32, u32) {
+        let mut completed = 0;
+        let mut expected = 0;
+        for date in (self.today - Duration::days(days - 1)).iter_days().take(days as usize) {
+            match self.status(date) {
+                DayStatus::CheckedIn => {
+                    completed += 1;
+                    expected += 1;
+                }
+                DayStatus::Missed | DayStatus::Grace | DayStatus::Frozen => expected += 1,
+                _ => {}
+            }
+        }
+        (completed, expected)
+    }
+
+    /// The month containing `month_start` as rows of Monday-first weeks; `None` pads days outside the month
+    pub fn month_grid(&self, month_start: NaiveDate) -> Vec<[Option<DayStatus>; 7]> {
+        let mut weeks = Vec::new();
+        let mut week = [None; 7];
+        for date in month_start.iter_days().take_while(|date| date.month() == month_start.month()) {
+            let weekday = date.weekday().num_days_from_monday() as usize;
+            week[weekday] = Some(self.status(date));
+            if weekday == 6 {
+                weeks.push(week);
+                week = [None; 7];
+            }
+        }
+        if week.iter().any(Option::is_some) {
+            weeks.push(week);
+        }
+        weeks
+    }
+
+    /// The month grid as lines of emoji, with a weekday header
+    pub fn render_month(&self, month_start: NaiveDate) -> String {
+        let mut lines = vec!["Mo Tu We Th Fr Sa Su".to_string()];
+        for week in self.month_grid(month_start) {
+            lines.push(
+                week.iter()
+                    .map(|day| day.map(DayStatus::emoji).unwrap_or("▫️"))
+                    .collect::<Vec<_>>()
+                    .join(""),
+            );
+        }
+        lines.join("\n")
+    }
+}
+
+/// Format a completion pair like "24/28 (86%)"
+pub fn format_completion((completed, expected): (u32, u32)) -> String {
+    if expected == 0 {
+        "-".to_string()
+    } else {
+        format!("{}/{} ({}%)", completed, expected, completed * 100 / expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{CheckinRecord, PausePeriod};
+    use chrono::{TimeZone, Utc};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, day).unwrap()
+    }
+
+    fn guild() -> BotData {
+        let mut data = BotData::default();
+        let created = Utc.with_ymd_and_hms(2025, 11, 3, 12, 0, 0).unwrap();
+        let mut user = UserData::new("2000".to_string(), created);
+        let mut goal = Goal::new("read".to_string(), "Read".to_string(), created);
+        goal.grace_days = vec![date(6)];
+        user.goals.push(goal);
+        user.pauses = vec![PausePeriod { from: date(10), to: date(11) }];
+        data.add_or_update_user("1000".to_string(), user);
+        for (day, goal) in [(3, "read"), (4, "read"), (5, "read"), (7, "read"), (12, "read"), (13, "exercise")] {
+            data.add_checkin("1000".to_string(), CheckinRecord {
+                user_id: "2000".to_string(),
+                checkin_date: date(day),
+                message_id: None,
+                thread_id: None,
+                daily_post_id: None,
+                goal: Some(goal.to_string()),
+                created_at: created,
+                before: None,
+            });
+        }
+        data.add_missed_post_day("1000".to_string(), date(8));
+        data
+    }
+
+    #[test]
+    fn days_are_classified_from_checkins_grace_pauses_and_missed_posts() {
+        let data = guild();
+        let user = data.get_user("1000", "2000").unwrap();
+        let history = for_goal(&data, "1000", user, user.goal("read").unwrap(), date(14));
+
+        assert_eq!(history.status(date(2)), DayStatus::Untracked);
+        assert_eq!(history.status(date(3)), DayStatus::CheckedIn);
+        assert_eq!(history.status(date(6)), DayStatus::Grace);
+        assert_eq!(history.status(date(8)), DayStatus::NotScheduled);
+        assert_eq!(history.status(date(9)), DayStatus::Missed);
+        assert_eq!(history.status(date(10)), DayStatus::Paused);
+        assert_eq!(history.status(date(14)), DayStatus::Pending);
+        assert_eq!(history.status(date(15)), DayStatus::Untracked);
+    }
+
+    #[test]
+    fn completion_only_expects_scheduled_unpaused_days() {
+        let data = guild();
+        let user = data.get_user("1000", "2000").unwrap();
+        let history = for_goal(&data, "1000", user, user.goal("read").unwrap(), date(14));
+
+        // 3rd-13th: 5 check-ins; the 6th, 9th and 13th missed (the 13th's check-in was for another goal); 8th, 10th and 11th not expected
+        assert_eq!(history.completion(30), (5, 8));
+        assert_eq!(format_completion(history.completion(30)), "5/8 (62%)");
+    }
+
+    #[test]
+    fn month_grids_start_on_monday() {
+        let data = guild();
+        let user = data.get_user("1000", "2000").unwrap();
+        let history = for_goal(&data, "1000", user, user.goal("read").unwrap(), date(14));
+
+        // November 2025 starts on a Saturday and ends on a Sunday
+        let grid = history.month_grid(date(1));
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid[0][..5], [None; 5]);
+        assert_eq!(grid[1][0], Some(DayStatus::CheckedIn)); // Monday the 3rd
+        assert_eq!(grid[4][6], Some(DayStatus::Untracked)); // Sunday the 30th
+    }
+}
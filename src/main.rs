@@ -1,14 +1,23 @@
 use anyhow::Result;
+use std::sync::atomic::AtomicBool;
 use serenity::prelude::*;
-use tracing::{info, warn, error};
+use tracing::{info, error};
 
 mod data;
+mod migrations;
 mod bot;
+mod clock;
 mod handler;
 mod commands;
 mod utils;
+mod platform;
+mod permissions;
 mod scheduler;
+mod schedule;
 mod streaks;
+mod history;
+mod heatmap;
+mod storage;
 
 use data::BotData;
 use bot::Bot;
@@ -28,17 +37,13 @@ async fn main() -> Result<()> {
 
     info!("Starting Daily Check-in Bot...");
 
-    // Load bot data
-    let bot_data = match BotData::load().await {
-        Ok(data) => {
-            info!("Successfully loaded bot data");
-            data
-        },
-        Err(e) => {
-            warn!("Failed to load bot data, starting fresh: {}", e);
-            BotData::default()
-        }
-    };
+    // Open the configured storage backend and load bot data
+    let storage = storage::from_env().await?;
+    // Refuse to start rather than silently resetting everyone's streaks
+    let bot_data = BotData::load(storage)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to load bot data: {}", e))?;
+    info!("Successfully loaded bot data");
 
     let bot = Bot::new(bot_data);
 
@@ -46,10 +51,12 @@ async fn main() -> Result<()> {
     let token = std::env::var("DISCORD_TOKEN")
         .map_err(|_| anyhow::anyhow!("DISCORD_TOKEN environment variable is required"))?;
 
-    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    // GUILDS fills the guild cache that permission checks read roles and owners from
+    let intents = GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
 
     let handler = Handler {
         data: bot.data.clone(),
+        scheduler_started: AtomicBool::new(false),
     };
 
     let mut client = Client::builder(&token, intents)
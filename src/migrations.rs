@@ -0,0 +1,265 @@
+use anyhow::Result;
+use crate::data::goal_slug;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Version of the persisted data document written by this build.
+/// Bump it together with a new entry in `MIGRATIONS` whenever the shape of
+/// `BotData` changes in a way older documents can't be read as.
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`
+const MIGRATIONS: &[Migration] = &[
+    v0_to_v1,
+    v1_to_v2,
+    v2_to_v3,
+];
+
+/// Upgrade a persisted document to `CURRENT_SCHEMA_VERSION`, one step at a time.
+/// Documents without a `schema_version` field predate versioning and count as version 0.
+///
+/// Returns the upgraded document and whether any migration was applied.
+pub fn migrate(mut document: Value) -> Result<(Value, bool)> {
+    let root = document
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Data document is not a JSON object"))?;
+
+    let version = match root.get("schema_version") {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid schema_version: {}", value))?,
+    };
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(anyhow::anyhow!(
+            "Data was written by a newer version of the bot (schema version {}, this build supports up to {})",
+            version,
+            CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    for (from, step) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        step(root)?;
+        root.insert("schema_version".to_string(), Value::from(from as u32 + 1));
+        info!("Migrated data document from schema version {} to {}", from, from + 1);
+    }
+
+    Ok((document, version < CURRENT_SCHEMA_VERSION))
+}
+
+/// Version 1 introduced the per-guild check-in history log
+fn v0_to_v1(root: &mut Map<String, Value>) -> Result<()> {
+    root.entry("checkins").or_insert_with(|| Value::Object(Map::new()));
+    Ok(())
+}
+
+/// Version 2 tracks the days the bot itself failed to post, which never break streaks
+fn v1_to_v2(root: &mut Map<String, Value>) -> Result<()> {
+    root.entry("missed_post_days").or_insert_with(|| Value::Object(Map::new()));
+    Ok(())
+}
+
+/// Version 3 lets users track several goals: each user's goal and its streak state
+/// move into a single-entry `goals` list, and check-ins record which goal they were for
+fn v2_to_v3(root: &mut Map<String, Value>) -> Result<()> {
+    const GOAL_FIELDS: &[&str] = &[
+        "current_streak",
+        "longest_streak",
+        "last_checkin_date",
+        "grace_period_start",
+        "grace_days",
+        "freeze_tokens",
+        "freeze_history",
+    ];
+
+    let mut goal_names = HashMap::new(); // (guild_id, user_id) -> goal name
+    if let Some(Value::Object(guilds)) = root.get_mut("users") {
+        for (guild_id, guild_users) in guilds.iter_mut() {
+            let guild_users = guild_users
+                .as_object_mut()
+                .ok_or_else(|| anyhow::anyhow!("Users of guild {} are not an object", guild_id))?;
+            for (user_id, user) in guild_users.iter_mut() {
+                let user = user
+                    .as_object_mut()
+                    .ok_or_else(|| anyhow::anyhow!("User {} in guild {} is not an object", user_id, guild_id))?;
+
+                // Already has a goals list, e.g. written by a newer build before it was versioned
+                if matches!(user.get("goals"), Some(Value::Array(_))) {
+                    continue;
+                }
+                // Nothing to move: the user has no goal yet
+                if !user.contains_key("goal") && !GOAL_FIELDS.iter().any(|field| user.contains_key(*field)) {
+                    user.insert("goals".to_string(), Value::Array(Vec::new()));
+                    continue;
+                }
+
+                let description = match user.remove("goal") {
+                    Some(Value::String(description)) => description,
+                    _ => String::new(),
+                };
+                let name = goal_slug(&description);
+
+                let mut goal = Map::new();
+                goal.insert("name".to_string(), Value::from(name.clone()));
+                goal.insert("description".to_string(), Value::from(description));
+                for field in GOAL_FIELDS {
+                    if let Some(value) = user.remove(*field) {
+                        goal.insert(field.to_string(), value);
+                    }
+                }
+                for field in ["is_active", "created_at", "updated_at"] {
+                    if let Some(value) = user.get(field) {
+                        goal.insert(field.to_string(), value.clone());
+                    }
+                }
+                user.insert("goals".to_string(), Value::Array(vec![Value::Object(goal)]));
+
+                goal_names.insert((guild_id.clone(), user_id.clone()), name);
+            }
+        }
+    }
+
+    if let Some(Value::Object(guilds)) = root.get_mut("checkins") {
+        for (guild_id, records) in guilds.iter_mut() {
+            for record in records.as_array_mut().into_iter().flatten() {
+                let Some(record) = record.as_object_mut() else {
+                    continue;
+                };
+                let user_id = record.get("user_id").and_then(Value::as_str).unwrap_or_default().to_string();
+                if let Some(name) = goal_names.get(&(guild_id.clone(), user_id)) {
+                    record.entry("goal").or_insert_with(|| Value::from(name.clone()));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::BotData;
+    use chrono::NaiveDate;
+
+    fn fixture(version: u32) -> Value {
+        let path = format!(
+            "{}/tests/fixtures/schema_v{}.json",
+            env!("CARGO_MANIFEST_DIR"),
+            version
+        );
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Missing fixture for schema version {} ({}): {}", version, path, e));
+        serde_json::from_str(&content).unwrap()
+    }
+
+    #[test]
+    fn every_historical_version_has_a_loadable_fixture() {
+        for version in 0..=CURRENT_SCHEMA_VERSION {
+            let data = BotData::from_document(fixture(version))
+                .unwrap_or_else(|e| panic!("Schema version {} fixture failed to load: {}", version, e));
+            assert_eq!(data.schema_version, CURRENT_SCHEMA_VERSION);
+        }
+    }
+
+    #[test]
+    fn v0_documents_keep_users_and_servers() {
+        let data = BotData::from_document(fixture(0)).unwrap();
+
+        let user = data.get_user("1000", "2000").unwrap();
+        assert_eq!(user.goals.len(), 1);
+        assert_eq!(user.goals[0].description, "Exercise for 30 minutes daily");
+        assert_eq!(user.goals[0].current_streak, 12);
+        assert_eq!(user.goals[0].last_checkin_date, NaiveDate::from_ymd_opt(2025, 11, 16));
+        assert_eq!(data.get_server_config("1000").unwrap().daily_time, "09:00");
+        assert!(data.daily_posts.contains_key("1000"));
+        assert!(data.get_checkins("1000").is_empty());
+    }
+
+    #[test]
+    fn v1_documents_keep_checkin_history() {
+        let data = BotData::from_document(fixture(1)).unwrap();
+
+        let records = data.get_user_checkins("1000", "2000");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].message_id.as_deref(), Some("4002"));
+    }
+
+    #[test]
+    fn v2_documents_keep_missed_post_days_and_catch_up_posts() {
+        let data = BotData::from_document(fixture(2)).unwrap();
+
+        assert_eq!(data.missed_post_days["1000"], vec![NaiveDate::from_ymd_opt(2025, 11, 12).unwrap()]);
+        let post = &data.daily_posts["1000"];
+        assert_eq!(post.catch_up_for, Some(NaiveDate::from_ymd_opt(2025, 11, 16).unwrap()));
+    }
+
+    #[test]
+    fn v2_users_get_a_single_named_goal_and_checkins_point_at_it() {
+        let data = BotData::from_document(fixture(2)).unwrap();
+
+        let user = data.get_user("1000", "2000").unwrap();
+        assert_eq!(user.goals.len(), 1);
+        let goal = &user.goals[0];
+        assert_eq!(goal.name, "exercise");
+        assert_eq!(goal.current_streak, 12);
+        assert!(goal.is_active);
+        assert!(!data.get_user("1000", "2001").unwrap().goals[0].is_active);
+
+        let records = data.get_user_checkins("1000", "2000");
+        assert!(records.iter().all(|record| record.goal.as_deref() == Some("exercise")));
+    }
+
+    #[test]
+    fn v3_documents_keep_multiple_goals() {
+        let data = BotData::from_document(fixture(3)).unwrap();
+
+        let user = data.get_user("1000", "2000").unwrap();
+        let names: Vec<_> = user.goals.iter().map(|goal| goal.name.as_str()).collect();
+        assert_eq!(names, vec!["exercise", "reading"]);
+        assert_eq!(user.goal("reading").unwrap().current_streak, 4);
+        let reading = data
+            .get_user_checkins("1000", "2000")
+            .into_iter()
+            .filter(|record| record.goal.as_deref() == Some("reading"))
+            .count();
+        assert_eq!(reading, 1);
+    }
+
+    #[test]
+    fn v2_to_v3_leaves_existing_goal_lists_alone() {
+        let mut document = fixture(3);
+        let before = document.clone();
+        v2_to_v3(document.as_object_mut().unwrap()).unwrap();
+        assert_eq!(document["users"], before["users"]);
+        assert_eq!(document["checkins"], before["checkins"]);
+
+        // A user without a goal gets an empty list rather than a blank goal
+        let mut document = fixture(2);
+        let user = document["users"]["1000"]["2000"].as_object_mut().unwrap();
+        user.retain(|field, _| ["user_id", "is_active", "created_at", "updated_at"].contains(&field.as_str()));
+        v2_to_v3(document.as_object_mut().unwrap()).unwrap();
+        assert_eq!(document["users"]["1000"]["2000"]["goals"], Value::Array(Vec::new()));
+    }
+
+    #[test]
+    fn migrating_reports_whether_anything_changed() {
+        let (_, migrated) = migrate(fixture(0)).unwrap();
+        assert!(migrated);
+
+        let (_, migrated) = migrate(fixture(CURRENT_SCHEMA_VERSION)).unwrap();
+        assert!(!migrated);
+    }
+
+    #[test]
+    fn newer_documents_are_rejected() {
+        let mut document = fixture(CURRENT_SCHEMA_VERSION);
+        document["schema_version"] = Value::from(CURRENT_SCHEMA_VERSION + 1);
+        assert!(migrate(document).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{data::ServerConfig, platform::Platform};
+use anyhow::Result;
+use serenity::model::{
+    application::CommandInteraction,
+    id::{GuildId, RoleId, UserId},
+    permissions::Permissions,
+};
+
+/// How much of the bot's server configuration a member may change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Access {
+    Member,
+    Manager, // holds one of the server's manager roles: may run the admin commands
+    Admin,   // server owner or administrator: may also choose the manager roles
+}
+
+/// What an interaction says about the member who used it
+#[derive(Debug, Clone)]
+pub struct Invoker {
+    pub user_id: UserId,
+    pub roles: Vec<RoleId>,
+    pub permissions: Option<Permissions>, // resolved by Discord, counting ownership; absent outside guilds
+}
+
+impl From<&CommandInteraction> for Invoker {
+    fn from(command: &CommandInteraction) -> Self {
+        let member = command.member.as_deref();
+        Self {
+            user_id: command.user.id,
+            roles: member.map(|member| member.roles.clone()).unwrap_or_default(),
+            permissions: member.and_then(|member| member.permissions),
+        }
+    }
+}
+
+/// The invoker's access in a guild. The permissions Discord resolves into the interaction
+/// decide on their own; only without them are the guild's owner and roles looked up.
+pub async fn access(platform: &dyn Platform, config: Option<&ServerConfig>, guild_id: GuildId, invoker: &Invoker) -> Result<Access> {
+    let holds_manager_role = |roles: &[RoleId]| {
+        config.is_some_and(|config| roles.iter().any(|role| config.manager_role_ids.contains(&role.to_string())))
+    };
+
+    if let Some(permissions) = invoker.permissions {
+        return Ok(if permissions.administrator() {
+            Access::Admin
+        } else if holds_manager_role(&invoker.roles) {
+            Access::Manager
+        } else {
+            Access::Member
+        });
+    }
+
+    let guild = platform.fetch_guild(guild_id).await?;
+    let roles = platform.member_roles(guild_id, invoker.user_id).await?;
+    // Every member also holds @everyone, whose id is the guild's
+    let everyone = RoleId::new(guild_id.get());
+    let is_admin = guild.owner_id == invoker.user_id
+        || roles.iter().chain([&everyone]).any(|role| {
+            guild
+                .role_permissions
+                .get(role)
+                .is_some_and(|permissions| permissions.administrator())
+        });
+    Ok(if is_admin {
+        Access::Admin
+    } else if holds_manager_role(&roles) {
+        Access::Manager
+    } else {
+        Access::Member
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::{fake::FakePlatform, GuildInfo};
+    use std::collections::HashMap;
+
+    const GUILD: GuildId = GuildId::new(1000);
+    const MODS: RoleId = RoleId::new(500);
+    const ADMINS: RoleId = RoleId::new(501);
+
+    fn config_with_mods() -> ServerConfig {
+        let mut config = ServerConfig::new(GUILD.to_string(), chrono::Utc::now());
+        config.manager_role_ids = vec![MODS.to_string()];
+        config
+    }
+
+    fn invoker(roles: Vec<RoleId>, permissions: Option<Permissions>) -> Invoker {
+        Invoker { user_id: UserId::new(2000), roles, permissions }
+    }
+
+    #[tokio::test]
+    async fn interaction_permissions_decide_without_lookups() {
+        let platform = FakePlatform::default();
+        let config = config_with_mods();
+        let config = Some(&config);
+
+        let admin = invoker(Vec::new(), Some(Permissions::ADMINISTRATOR));
+        assert_eq!(access(&platform, config, GUILD, &admin).await.unwrap(), Access::Admin);
+        let moderator = invoker(vec![MODS], Some(Permissions::SEND_MESSAGES));
+        assert_eq!(access(&platform, config, GUILD, &moderator).await.unwrap(), Access::Manager);
+        let other = invoker(vec![ADMINS], Some(Permissions::MANAGE_GUILD));
+        assert_eq!(access(&platform, config, GUILD, &other).await.unwrap(), Access::Member);
+        assert_eq!(platform.lookups(), 0);
+
+        // Without manager roles configured, only admins get in
+        let unconfigured = access(&platform, None, GUILD, &invoker(vec![MODS], Some(Permissions::empty()))).await.unwrap();
+        assert_eq!(unconfigured, Access::Member);
+    }
+
+    #[tokio::test]
+    async fn guild_roles_are_looked_up_when_the_interaction_has_no_permissions() {
+        let platform = FakePlatform::default();
+        platform.add_guild(GUILD, GuildInfo {
+            owner_id: UserId::new(1),
+            role_permissions: HashMap::from([(MODS, Permissions::empty()), (ADMINS, Permissions::ADMINISTRATOR)]),
+        });
+        let config = config_with_mods();
+
+        platform.set_member_roles(GUILD, UserId::new(2000), vec![MODS]);
+        assert_eq!(access(&platform, Some(&config), GUILD, &invoker(Vec::new(), None)).await.unwrap(), Access::Manager);
+
+        platform.set_member_roles(GUILD, UserId::new(2000), vec![ADMINS]);
+        assert_eq!(access(&platform, Some(&config), GUILD, &invoker(Vec::new(), None)).await.unwrap(), Access::Admin);
+
+        // Administrator granted to @everyone applies to members without any roles
+        platform.add_guild(GUILD, GuildInfo {
+            owner_id: UserId::new(1),
+            role_permissions: HashMap::from([(RoleId::new(GUILD.get()), Permissions::ADMINISTRATOR)]),
+        });
+        platform.set_member_roles(GUILD, UserId::new(2000), Vec::new());
+        assert_eq!(access(&platform, Some(&config), GUILD, &invoker(Vec::new(), None)).await.unwrap(), Access::Admin);
+
+        let owner = Invoker { user_id: UserId::new(1), roles: Vec::new(), permissions: None };
+        platform.set_member_roles(GUILD, owner.user_id, Vec::new());
+        assert_eq!(access(&platform, Some(&config), GUILD, &owner).await.unwrap(), Access::Admin);
+    }
+}
@@ -0,0 +1,91 @@
+use super::{GuildInfo, IncomingMessage, OutgoingMessage, Platform};
+use anyhow::Result;
+use serenity::{
+    async_trait,
+    builder::{CreateAllowedMentions, CreateMessage, CreateThread},
+    cache::Cache,
+    http::Http,
+    model::{
+        channel::{ChannelType, ReactionType},
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    },
+    prelude::Context,
+};
+use std::sync::Arc;
+
+/// Talks to Discord over serenity's HTTP client, reading guilds and members
+/// from the cache when it has them
+pub struct DiscordPlatform {
+    http: Arc<Http>,
+    cache: Arc<Cache>,
+}
+
+impl DiscordPlatform {
+    pub fn new(ctx: &Context) -> Self {
+        Self {
+            http: ctx.http.clone(),
+            cache: ctx.cache.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Platform for DiscordPlatform {
+    async fn send_message(&self, channel_id: ChannelId, message: OutgoingMessage) -> Result<MessageId> {
+        let mut builder = CreateMessage::new().components(message.components);
+        if let Some(content) = message.content {
+            builder = builder.content(content);
+        }
+        if let Some(embed) = message.embed {
+            builder = builder.add_embed(embed);
+        }
+        if let Some(reply_to) = message.reply_to {
+            builder = builder.reference_message((channel_id, reply_to));
+        }
+        if message.silent {
+            builder = builder.allowed_mentions(CreateAllowedMentions::new());
+        }
+        Ok(channel_id.send_message(&self.http, builder).await?.id)
+    }
+
+    async fn create_thread(&self, channel_id: ChannelId, name: &str) -> Result<ChannelId> {
+        let thread = channel_id
+            .create_thread(&self.http, CreateThread::new(name).kind(ChannelType::PublicThread))
+            .await?;
+        Ok(thread.id)
+    }
+
+    async fn fetch_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<IncomingMessage> {
+        let message = channel_id.message(&self.http, message_id).await?;
+        Ok(IncomingMessage::from(&message))
+    }
+
+    async fn react(&self, channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()> {
+        channel_id.create_reaction(&self.http, message_id, emoji).await?;
+        Ok(())
+    }
+
+    async fn remove_reaction(&self, channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()> {
+        channel_id.delete_reaction(&self.http, message_id, None, emoji).await?;
+        Ok(())
+    }
+
+    async fn fetch_guild(&self, guild_id: GuildId) -> Result<GuildInfo> {
+        if let Some(guild) = guild_id.to_guild_cached(&self.cache) {
+            return Ok(GuildInfo {
+                owner_id: guild.owner_id,
+                role_permissions: guild.roles.iter().map(|(id, role)| (*id, role.permissions)).collect(),
+            });
+        }
+        let guild = self.http.get_guild(guild_id).await?;
+        Ok(GuildInfo {
+            owner_id: guild.owner_id,
+            role_permissions: guild.roles.iter().map(|(id, role)| (*id, role.permissions)).collect(),
+        })
+    }
+
+    async fn member_roles(&self, guild_id: GuildId, user_id: UserId) -> Result<Vec<RoleId>> {
+        let member = guild_id.member((&self.cache, self.http.as_ref()), user_id).await?;
+        Ok(member.roles)
+    }
+}
@@ -0,0 +1,309 @@
+use super::{GuildInfo, IncomingMessage, OutgoingMessage, Platform};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serenity::{
+    async_trait,
+    model::{
+        channel::ReactionType,
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// A message the bot sent through the fake platform
+#[derive(Debug, Clone)]
+pub struct SentMessage {
+    pub id: MessageId,
+    pub channel_id: ChannelId,
+    pub message: OutgoingMessage,
+}
+
+/// A thread the bot started
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeThread {
+    pub id: ChannelId,
+    pub channel_id: ChannelId,
+    pub name: String,
+}
+
+/// An in-memory stand-in for Discord that records everything the bot does,
+/// and lets tests post messages as members
+#[derive(Debug, Default)]
+pub struct FakePlatform {
+    last_id: AtomicU64,
+    sent: Mutex<Vec<SentMessage>>,
+    threads: Mutex<Vec<FakeThread>>,
+    posted: Mutex<HashMap<MessageId, IncomingMessage>>, // messages posted by members
+    reactions: Mutex<Vec<(MessageId, ReactionType)>>,   // the bot's current reactions
+    guilds: Mutex<HashMap<GuildId, GuildInfo>>,
+    member_roles: Mutex<HashMap<(GuildId, UserId), Vec<RoleId>>>,
+    lookups: AtomicUsize, // guild and member requests made
+}
+
+impl FakePlatform {
+    fn next_id(&self) -> u64 {
+        self.last_id.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// A member posts `content` in `channel_id` at `timestamp`
+    pub fn post(&self, guild_id: GuildId, channel_id: ChannelId, author_id: UserId, content: &str, timestamp: DateTime<Utc>) -> IncomingMessage {
+        let message = IncomingMessage {
+            id: MessageId::new(self.next_id()),
+            channel_id,
+            guild_id: Some(guild_id),
+            author_id,
+            author_is_bot: false,
+            content: content.to_string(),
+            attachments: 0,
+            timestamp: Some(timestamp),
+            own_reactions: Vec::new(),
+        };
+        self.posted.lock().unwrap().insert(message.id, message.clone());
+        message
+    }
+
+    /// A member edits one of their messages
+    pub fn edit(&self, message_id: MessageId, content: &str) {
+        if let Some(message) = self.posted.lock().unwrap().get_mut(&message_id) {
+            message.content = content.to_string();
+        }
+    }
+
+    /// Messages the bot sent to a channel or thread, oldest first
+    pub fn sent_to(&self, channel_id: ChannelId) -> Vec<SentMessage> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|sent| sent.channel_id == channel_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn threads(&self) -> Vec<FakeThread> {
+        self.threads.lock().unwrap().clone()
+    }
+
+    pub fn add_guild(&self, guild_id: GuildId, guild: GuildInfo) {
+        self.guilds.lock().unwrap().insert(guild_id, guild);
+    }
+
+    pub fn set_member_roles(&self, guild_id: GuildId, user_id: UserId, roles: Vec<RoleId>) {
+        self.member_roles.lock().unwrap().insert((guild_id, user_id), roles);
+    }
+
+    /// How many times guilds or members were requested
+    pub fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::SeqCst)
+    }
+
+    /// The bot's reactions on a message
+    pub fn reactions_on(&self, message_id: MessageId) -> Vec<ReactionType> {
+        self.reactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _)| *id == message_id)
+            .map(|(_, reaction)| reaction.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Platform for FakePlatform {
+    async fn send_message(&self, channel_id: ChannelId, message: OutgoingMessage) -> Result<MessageId> {
+        let id = MessageId::new(self.next_id());
+        self.sent.lock().unwrap().push(SentMessage { id, channel_id, message });
+        Ok(id)
+    }
+
+    async fn create_thread(&self, channel_id: ChannelId, name: &str) -> Result<ChannelId> {
+        let id = ChannelId::new(self.next_id());
+        self.threads.lock().unwrap().push(FakeThread { id, channel_id, name: name.to_string() });
+        Ok(id)
+    }
+
+    async fn fetch_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<IncomingMessage> {
+        let mut message = self
+            .posted
+            .lock()
+            .unwrap()
+            .get(&message_id)
+            .filter(|message| message.channel_id == channel_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown message {}", message_id))?;
+        // Like Discord, messages fetched by id don't say which guild they're in
+        message.guild_id = None;
+        message.own_reactions = self.reactions_on(message_id);
+        Ok(message)
+    }
+
+    async fn react(&self, _channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()> {
+        let mut reactions = self.reactions.lock().unwrap();
+        if !reactions.contains(&(message_id, emoji.clone())) {
+            reactions.push((message_id, emoji));
+        }
+        Ok(())
+    }
+
+    async fn remove_reaction(&self, _channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()> {
+        self.reactions
+            .lock()
+            .unwrap()
+            .retain(|reaction| *reaction != (message_id, emoji.clone()));
+        Ok(())
+    }
+
+    async fn fetch_guild(&self, guild_id: GuildId) -> Result<GuildInfo> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        self.guilds
+            .lock()
+            .unwrap()
+            .get(&guild_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown guild {}", guild_id))
+    }
+
+    async fn member_roles(&self, guild_id: GuildId, user_id: UserId) -> Result<Vec<RoleId>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        self.member_roles
+            .lock()
+            .unwrap()
+            .get(&(guild_id, user_id))
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown member {}", user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bot::SharedBotData,
+        clock::{Clock, FakeClock},
+        commands::user,
+        data::{BotData, GoalFrequency, ServerConfig},
+        scheduler::DailyScheduler,
+        streaks::StreakManager,
+    };
+    use chrono::{Duration, NaiveDate};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    const GUILD: GuildId = GuildId::new(1000);
+    const CHANNEL: ChannelId = ChannelId::new(3000);
+    const MEMBER: UserId = UserId::new(2000);
+
+    /// A guild posting at 09:00 UTC in `CHANNEL`, with the clock at midnight before the first post
+    fn guild() -> (SharedBotData, Arc<FakeClock>) {
+        let clock = FakeClock::new(NaiveDate::from_ymd_opt(2025, 11, 3).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc());
+        let mut data = BotData::default();
+        data.set_clock(clock.clone());
+        let mut config = ServerConfig::new(GUILD.to_string(), clock.now());
+        config.checkin_channel_id = Some(CHANNEL.to_string());
+        data.add_or_update_server(config);
+        (Arc::new(RwLock::new(data)), clock)
+    }
+
+    /// Move the clock to the day's post time and let the scheduler post, returning the thread
+    async fn post(platform: &FakePlatform, data: &SharedBotData, clock: &FakeClock) -> ChannelId {
+        let now = clock.now();
+        let fire_time = now.date_naive().and_hms_opt(9, 0, 0).unwrap().and_utc();
+        clock.set(fire_time);
+        DailyScheduler::new(data.clone())
+            .run_scheduled_post(platform, &GUILD.to_string(), fire_time)
+            .await
+            .unwrap();
+        platform.threads().last().unwrap().id
+    }
+
+    fn streak(data: &BotData) -> u32 {
+        data.get_user(&GUILD.to_string(), &MEMBER.to_string()).unwrap().goal("read").unwrap().current_streak
+    }
+
+    #[tokio::test]
+    async fn register_post_reply_and_keep_a_streak() {
+        let platform = FakePlatform::default();
+        let (data, clock) = guild();
+        let streaks = StreakManager::new(data.clone());
+
+        let confirmation = user::add_goal(&data, &GUILD.to_string(), &MEMBER.to_string(), "Read 20 pages".to_string(), None, GoalFrequency::Daily)
+            .await
+            .unwrap();
+        assert!(matches!(confirmation, user::GoalRegistration::Saved(message) if message.contains("Read 20 pages")));
+        {
+            // The new member and goal are stamped with the bot's clock
+            let data = data.read().await;
+            let member = data.get_user(&GUILD.to_string(), &MEMBER.to_string()).unwrap();
+            assert_eq!((member.created_at, member.goal("read").unwrap().created_at), (clock.now(), clock.now()));
+        }
+
+        for day in 1..=3 {
+            let thread = post(&platform, &data, &clock).await;
+
+            // The post goes to the channel, and its thread pings the member
+            let daily_post = platform.sent_to(CHANNEL).pop().unwrap();
+            assert!(daily_post.message.embed.is_some());
+            assert_eq!(daily_post.message.components.len(), 1);
+            assert_eq!(data.read().await.daily_posts[&GUILD.to_string()].message_id, daily_post.id.to_string());
+            let pings = platform.sent_to(thread);
+            assert!(pings[0].message.content.as_deref().unwrap().contains(&format!("<@{}>", MEMBER)));
+
+            clock.advance(Duration::hours(3));
+            let reply = platform.post(GUILD, thread, MEMBER, "Read a chapter", clock.now());
+            streaks.process_message(&platform, &reply).await.unwrap();
+            assert_eq!(platform.reactions_on(reply.id), vec![ReactionType::from('✅')]);
+            assert_eq!(streak(&*data.read().await), day);
+
+            // Chatting in the thread afterwards doesn't count twice
+            let chat = platform.post(GUILD, thread, MEMBER, "Nice work everyone", clock.now());
+            streaks.process_message(&platform, &chat).await.unwrap();
+            assert!(platform.reactions_on(chat.id).is_empty());
+
+            clock.advance(Duration::days(1));
+        }
+
+        // Skipping a day resets the streak at the next post
+        clock.advance(Duration::days(1));
+        post(&platform, &data, &clock).await;
+        assert_eq!(streak(&*data.read().await), 0);
+        assert_eq!(data.read().await.get_user_checkins(&GUILD.to_string(), &MEMBER.to_string()).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn edited_replies_are_checked_again() {
+        let platform = FakePlatform::default();
+        let (data, clock) = guild();
+        {
+            let mut data = data.write().await;
+            let mut config = data.get_server_config(&GUILD.to_string()).unwrap().clone();
+            config.checkin_requirements.min_length = 10;
+            data.add_or_update_server(config);
+        }
+        let streaks = StreakManager::new(data.clone());
+        user::add_goal(&data, &GUILD.to_string(), &MEMBER.to_string(), "Read 20 pages".to_string(), None, GoalFrequency::Daily)
+            .await
+            .unwrap();
+        let thread = post(&platform, &data, &clock).await;
+
+        // Too short: flagged and explained in a reply
+        let reply = platform.post(GUILD, thread, MEMBER, "read", clock.now());
+        streaks.process_message(&platform, &reply).await.unwrap();
+        assert_eq!(platform.reactions_on(reply.id), vec![ReactionType::from('❌')]);
+        let explanation = platform.sent_to(thread).pop().unwrap().message;
+        assert_eq!(explanation.reply_to, Some(reply.id));
+        assert_eq!(streak(&*data.read().await), 0);
+
+        // Fixed by an edit: the flag makes way for the usual reaction
+        platform.edit(reply.id, "Read two chapters");
+        streaks.process_message_update(&platform, GUILD, thread, reply.id).await.unwrap();
+        assert_eq!(platform.reactions_on(reply.id), vec![ReactionType::from('✅')]);
+        assert_eq!(streak(&*data.read().await), 1);
+    }
+}
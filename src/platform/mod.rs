@@ -0,0 +1,120 @@
+pub mod discord;
+#[cfg(test)]
+pub mod fake;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serenity::{
+    async_trait,
+    builder::{CreateActionRow, CreateEmbed},
+    model::{
+        channel::{Message, ReactionType},
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+        permissions::Permissions,
+    },
+};
+use std::collections::HashMap;
+
+pub use discord::DiscordPlatform;
+
+/// A message for the bot to send
+#[derive(Debug, Clone, Default)]
+pub struct OutgoingMessage {
+    pub content: Option<String>,
+    pub embed: Option<CreateEmbed>,
+    pub components: Vec<CreateActionRow>,
+    pub reply_to: Option<MessageId>,
+    pub silent: bool, // mentions in it don't notify anyone
+}
+
+impl OutgoingMessage {
+    pub fn text(content: impl Into<String>) -> Self {
+        Self {
+            content: Some(content.into()),
+            ..Self::default()
+        }
+    }
+
+    /// A reply to `message_id` that pings nobody, not even its author
+    pub fn reply(message_id: MessageId, content: impl Into<String>) -> Self {
+        Self {
+            reply_to: Some(message_id),
+            silent: true,
+            ..Self::text(content)
+        }
+    }
+}
+
+/// A message someone posted, with what the check-in logic needs to know about it
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    pub id: MessageId,
+    pub channel_id: ChannelId,
+    pub guild_id: Option<GuildId>, // not known for messages fetched by id
+    pub author_id: UserId,
+    pub author_is_bot: bool,
+    pub content: String,
+    pub attachments: usize,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub own_reactions: Vec<ReactionType>, // reactions the bot added itself
+}
+
+impl IncomingMessage {
+    /// Whether the bot reacted to the message with `emoji`
+    pub fn has_own_reaction(&self, emoji: &str) -> bool {
+        self.own_reactions.iter().any(|reaction| reaction.unicode_eq(emoji))
+    }
+}
+
+impl From<&Message> for IncomingMessage {
+    fn from(msg: &Message) -> Self {
+        Self {
+            id: msg.id,
+            channel_id: msg.channel_id,
+            guild_id: msg.guild_id,
+            author_id: msg.author.id,
+            author_is_bot: msg.author.bot,
+            content: msg.content.clone(),
+            attachments: msg.attachments.len(),
+            timestamp: DateTime::<Utc>::from_timestamp(msg.timestamp.unix_timestamp(), 0),
+            own_reactions: msg
+                .reactions
+                .iter()
+                .filter(|reaction| reaction.me)
+                .map(|reaction| reaction.reaction_type.clone())
+                .collect(),
+        }
+    }
+}
+
+/// A server's owner and what each of its roles is allowed to do
+#[derive(Debug, Clone)]
+pub struct GuildInfo {
+    pub owner_id: UserId,
+    pub role_permissions: HashMap<RoleId, Permissions>,
+}
+
+/// The chat platform the bot posts to and reads from.
+///
+/// The scheduler, check-in logic and permission checks only talk to the platform through
+/// this trait, so they can run against an in-memory fake in tests instead of Discord.
+#[async_trait]
+pub trait Platform: Send + Sync {
+    /// Send a message to a channel or thread, returning the new message's id
+    async fn send_message(&self, channel_id: ChannelId, message: OutgoingMessage) -> Result<MessageId>;
+
+    /// Start a public thread in a channel, returning the thread's id
+    async fn create_thread(&self, channel_id: ChannelId, name: &str) -> Result<ChannelId>;
+
+    async fn fetch_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<IncomingMessage>;
+
+    async fn react(&self, channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()>;
+
+    /// Take back one of the bot's own reactions
+    async fn remove_reaction(&self, channel_id: ChannelId, message_id: MessageId, emoji: ReactionType) -> Result<()>;
+
+    async fn fetch_guild(&self, guild_id: GuildId) -> Result<GuildInfo>;
+
+    /// The roles a member holds in a guild
+    async fn member_roles(&self, guild_id: GuildId, user_id: UserId) -> Result<Vec<RoleId>>;
+}
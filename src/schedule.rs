@@ -0,0 +1,212 @@
+use crate::data::ServerConfig;
+use anyhow::Result;
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use std::str::FromStr;
+
+/// When a server's daily check-in is posted, evaluated in the server's timezone.
+///
+/// Local times that daylight saving makes ambiguous follow `resolve_local`: a time that
+/// happens twice fires once, at its first occurrence, and a skipped one at the end of the gap.
+#[derive(Debug, Clone)]
+pub enum PostSchedule {
+    /// Once a day at a fixed local time (`ServerConfig::daily_time`)
+    Daily(NaiveTime),
+    /// Whenever a cron expression fires (`ServerConfig::cron_schedule`)
+    Cron(Box<Schedule>),
+}
+
+impl PostSchedule {
+    /// The schedule a server is configured with; a cron expression takes precedence over `daily_time`
+    pub fn for_server(config: &ServerConfig) -> Result<Self> {
+        match &config.cron_schedule {
+            Some(expression) => Self::parse_cron(expression),
+            None => Ok(Self::Daily(NaiveTime::parse_from_str(&config.daily_time, "%H:%M")?)),
+        }
+    }
+
+    /// Parse a cron expression. The standard five-field form (minute, hour, day of
+    /// month, month, day of week) is accepted as well as the six/seven-field form
+    /// with seconds (and years) that the `cron` crate uses.
+    pub fn parse_cron(expression: &str) -> Result<Self> {
+        let schedule = Schedule::from_str(&normalize_cron(expression))
+            .map_err(|e| anyhow::anyhow!("Invalid cron expression '{}': {}", expression, e))?;
+
+        // Reject expressions that can never fire (e.g. February 30th)
+        if schedule.upcoming(Utc).next().is_none() {
+            return Err(anyhow::anyhow!("Cron expression '{}' never fires", expression));
+        }
+
+        Ok(Self::Cron(Box::new(schedule)))
+    }
+
+    /// The first fire time strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        let local_after = after.with_timezone(&tz);
+        match self {
+            Self::Daily(time) => {
+                let mut date = local_after.date_naive();
+                // Two days ahead always suffices
+                for _ in 0..3 {
+                    if let Some(fire) = resolve_local(tz, date.and_time(*time)) {
+                        if fire > local_after {
+                            return Some(fire.with_timezone(&Utc));
+                        }
+                    }
+                    date = date.succ_opt()?;
+                }
+                None
+            }
+            // The cron crate drops local times that don't exist or repeat, so it runs on
+            // wall-clock times and each one is resolved here. Starting from the wall-clock
+            // time of `after` may give times already past when clocks fell back; skip those.
+            Self::Cron(schedule) => schedule
+                .after(&local_after.naive_local().and_utc())
+                .filter_map(|fire| resolve_local(tz, fire.naive_utc()))
+                .find(|fire| *fire > local_after)
+                .map(|fire| fire.with_timezone(&Utc)),
+        }
+    }
+
+    /// The next `count` fire times after `after`, in the server's timezone
+    pub fn upcoming(&self, after: DateTime<Utc>, tz: Tz, count: usize) -> Vec<DateTime<Tz>> {
+        let mut fires = Vec::with_capacity(count);
+        let mut cursor = after;
+        while fires.len() < count {
+            match self.next_after(cursor, tz) {
+                Some(fire) => {
+                    fires.push(fire.with_timezone(&tz));
+                    cursor = fire;
+                }
+                None => break,
+            }
+        }
+        fires
+    }
+
+    /// Whether the schedule fires at least once on the given local date
+    pub fn fires_on(&self, date: NaiveDate, tz: Tz) -> bool {
+        match self {
+            Self::Daily(_) => true,
+            Self::Cron(_) => {
+                let Some(start_of_day) = resolve_local(tz, date.and_time(NaiveTime::MIN)) else {
+                    return true;
+                };
+                let start = start_of_day.with_timezone(&Utc) - Duration::seconds(1);
+                self.next_after(start, tz)
+                    .map(|fire| fire.with_timezone(&tz).date_naive() == date)
+                    .unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// The instant a local date and time happens in `tz`. A time that happens twice when clocks
+/// fall back resolves to the first occurrence; a time skipped when they spring forward
+/// resolves to the end of the gap (e.g. 02:30 to 03:00).
+pub fn resolve_local(tz: Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(time) => Some(time),
+        LocalResult::Ambiguous(first, _) => Some(first),
+        LocalResult::None => (1..=24 * 60)
+            .map(|minutes| local + Duration::minutes(minutes))
+            .find_map(|later| tz.from_local_datetime(&later).earliest()),
+    }
+}
+
+/// Prepend a seconds field to standard five-field cron expressions
+fn normalize_cron(expression: &str) -> String {
+    let expression = expression.trim();
+    if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn five_field_cron_expressions_are_accepted() {
+        let schedule = PostSchedule::parse_cron("30 9 * * Mon-Fri").unwrap();
+        // Friday 2025-11-14 10:00 UTC -> next weekday post is Monday 09:30
+        let next = schedule.next_after(utc("2025-11-14T10:00:00Z"), chrono_tz::UTC).unwrap();
+        assert_eq!(next, utc("2025-11-17T09:30:00Z"));
+    }
+
+    #[test]
+    fn invalid_cron_expressions_are_rejected() {
+        assert!(PostSchedule::parse_cron("not a schedule").is_err());
+        assert!(PostSchedule::parse_cron("0 9 30 Feb *").is_err());
+    }
+
+    #[test]
+    fn cron_schedules_are_evaluated_in_the_server_timezone() {
+        let schedule = PostSchedule::parse_cron("0 9,18 * * *").unwrap();
+        let fires = schedule.upcoming(utc("2025-11-17T00:00:00Z"), chrono_tz::Asia::Tokyo, 3);
+        let fires: Vec<_> = fires.iter().map(|fire| fire.with_timezone(&Utc)).collect();
+        assert_eq!(fires, vec![
+            utc("2025-11-17T09:00:00Z"), // 18:00 JST
+            utc("2025-11-18T00:00:00Z"), // 09:00 JST
+            utc("2025-11-18T09:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn weekday_schedules_do_not_fire_on_weekends() {
+        let schedule = PostSchedule::parse_cron("0 9 * * Mon-Fri").unwrap();
+        let tz = chrono_tz::America::New_York;
+        assert!(schedule.fires_on(NaiveDate::from_ymd_opt(2025, 11, 14).unwrap(), tz));
+        assert!(!schedule.fires_on(NaiveDate::from_ymd_opt(2025, 11, 15).unwrap(), tz));
+        assert!(!schedule.fires_on(NaiveDate::from_ymd_opt(2025, 11, 16).unwrap(), tz));
+    }
+
+    #[test]
+    fn daily_schedules_fire_at_the_configured_local_time() {
+        let schedule = PostSchedule::Daily(NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        let next = schedule.next_after(utc("2025-11-17T15:00:00Z"), chrono_tz::America::New_York).unwrap();
+        assert_eq!(next, utc("2025-11-18T14:00:00Z"));
+    }
+
+    // New York springs forward on 2025-03-09 (02:00 -> 03:00) and falls back on 2025-11-02 (02:00 -> 01:00)
+    const NEW_YORK: Tz = chrono_tz::America::New_York;
+
+    #[test]
+    fn times_skipped_by_spring_forward_fire_at_the_end_of_the_gap() {
+        let daily = PostSchedule::Daily(NaiveTime::from_hms_opt(2, 30, 0).unwrap());
+        let cron = PostSchedule::parse_cron("30 2 * * *").unwrap();
+        for schedule in [daily, cron] {
+            let fire = schedule.next_after(utc("2025-03-09T05:00:00Z"), NEW_YORK).unwrap();
+            assert_eq!(fire, utc("2025-03-09T07:00:00Z")); // 03:00 EDT
+            assert_eq!(schedule.next_after(fire, NEW_YORK), Some(utc("2025-03-10T06:30:00Z")));
+            assert!(schedule.fires_on(NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(), NEW_YORK));
+        }
+    }
+
+    #[test]
+    fn times_repeated_by_fall_back_fire_once() {
+        let daily = PostSchedule::Daily(NaiveTime::from_hms_opt(1, 30, 0).unwrap());
+        let cron = PostSchedule::parse_cron("30 1 * * *").unwrap();
+        for schedule in [daily, cron] {
+            let fire = schedule.next_after(utc("2025-11-02T04:00:00Z"), NEW_YORK).unwrap();
+            assert_eq!(fire, utc("2025-11-02T05:30:00Z")); // the first 01:30, EDT
+            // Not again at the second 01:30 (06:30 UTC), but the next day in EST
+            assert_eq!(schedule.next_after(fire, NEW_YORK), Some(utc("2025-11-03T06:30:00Z")));
+        }
+    }
+
+    #[test]
+    fn frequent_cron_schedules_skip_the_repeated_hour() {
+        let schedule = PostSchedule::parse_cron("*/15 * * * *").unwrap();
+        // 01:10 EST, the second time around: every quarter hour until 02:00 has already fired
+        let fire = schedule.next_after(utc("2025-11-02T06:10:00Z"), NEW_YORK);
+        assert_eq!(fire, Some(utc("2025-11-02T07:00:00Z")));
+    }
+}
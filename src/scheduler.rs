@@ -1,184 +1,405 @@
-use crate::{bot::SharedBotData, data::DailyPost, streaks::StreakManager};
-use chrono::{DateTime, Utc, NaiveTime, Timelike};
+use crate::{bot::SharedBotData, commands::checkin, data::{DailyPost, Goal, GoalFrequency, ServerConfig, UserData}, platform::{OutgoingMessage, Platform}, schedule::PostSchedule, streaks::StreakManager};
+use chrono::{DateTime, NaiveDate, Utc};
 use chrono_tz::Tz;
 use serenity::{
-    builder::{CreateMessage, CreateThread, CreateEmbed},
+    builder::CreateEmbed,
     model::id::{ChannelId, GuildId},
-    prelude::*,
 };
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{info, error, debug};
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}, sync::{Arc, Mutex}};
+use tokio::sync::Mutex as AsyncMutex;
+use tracing::{info, error, debug, warn};
 
+/// Upper bound on a single sleep, so wall-clock jumps (NTP corrections,
+/// host suspend) are noticed even when the next post is hours away
+const MAX_SLEEP: chrono::Duration = chrono::Duration::minutes(10);
+
+/// A late post after downtime needs at least this long before the next regular
+/// post to be worth making; otherwise the missed day is just excused
+const MIN_CATCH_UP_WINDOW: chrono::Duration = chrono::Duration::hours(1);
+
+/// How many missed fire times are looked at after a long outage
+const MAX_MISSED_FIRES: usize = 366;
+
+/// A late check-in made up for a scheduled post the bot missed while it was offline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CatchUp {
+    pub missed_at: DateTime<Utc>, // when the missed post was scheduled
+    pub missed_date: NaiveDate,   // the day it was scheduled on, in the server's timezone
+    pub deadline: DateTime<Utc>,
+}
+
+/// What to do about the posts a guild missed during downtime
+#[derive(Debug, Default, PartialEq)]
+pub struct CatchUpPlan {
+    pub late_post: Option<CatchUp>,
+    pub missed_days: Vec<NaiveDate>, // days that get no post at all
+}
+
+impl CatchUpPlan {
+    /// Find the scheduled posts between the guild's last post and `now`. The most recent
+    /// one is made up with a late post that stays open until the next regular post;
+    /// every other missed day (and that one too, if the next post is imminent) is excused.
+    pub fn for_guild(config: &ServerConfig, last_posted_at: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        let (Ok(schedule), Ok(tz)) = (PostSchedule::for_server(config), config.timezone.parse::<Tz>()) else {
+            return Self::default();
+        };
+
+        let mut missed = Vec::new();
+        let mut cursor = last_posted_at;
+        while let Some(fire_time) = schedule.next_after(cursor, tz) {
+            if fire_time > now || missed.len() >= MAX_MISSED_FIRES {
+                break;
+            }
+            missed.push(fire_time);
+            cursor = fire_time;
+        }
+
+        let Some(latest) = missed.pop() else {
+            return Self::default();
+        };
+
+        let mut plan = Self::default();
+        match schedule.next_after(now, tz) {
+            Some(next_post) if next_post - now >= MIN_CATCH_UP_WINDOW => {
+                plan.late_post = Some(CatchUp {
+                    missed_at: latest,
+                    missed_date: latest.with_timezone(&tz).date_naive(),
+                    deadline: next_post,
+                });
+            }
+            _ => missed.push(latest),
+        }
+
+        plan.missed_days = missed.iter().map(|fire_time| fire_time.with_timezone(&tz).date_naive()).collect();
+        plan.missed_days.dedup();
+        plan
+    }
+}
+
+/// The next scheduled post instant of every configured guild, earliest first.
+///
+/// Each guild has a cursor: the instant after which its next post is looked up.
+/// Firing moves the cursor to the fire time itself rather than to "now", so a late
+/// wake-up still fires the due post once and never skips or repeats it.
+#[derive(Debug, Default)]
+pub struct FireQueue {
+    queue: BinaryHeap<Reverse<(DateTime<Utc>, String)>>,
+    cursors: HashMap<String, DateTime<Utc>>,
+    config_versions: HashMap<String, DateTime<Utc>>, // guild_id -> ServerConfig::updated_at
+}
+
+impl FireQueue {
+    /// Recompute the next fire instant of every guild from the current server configs
+    pub fn rebuild<'a>(&mut self, servers: impl IntoIterator<Item = &'a ServerConfig>, now: DateTime<Utc>) {
+        self.queue.clear();
+
+        for config in servers {
+            if config.checkin_channel_id.is_none() {
+                continue;
+            }
+
+            // New or changed configs only look for posts from now on
+            let changed = self.config_versions.get(&config.guild_id) != Some(&config.updated_at);
+            let cursor = self.cursors.entry(config.guild_id.clone()).or_insert(now);
+            if changed && *cursor < now {
+                *cursor = now;
+            }
+            self.config_versions.insert(config.guild_id.clone(), config.updated_at);
+
+            match Self::next_fire(config, *cursor) {
+                Some(fire_time) => self.queue.push(Reverse((fire_time, config.guild_id.clone()))),
+                None => warn!("Guild {} has no valid upcoming post time", config.guild_id),
+            }
+        }
+    }
+
+    /// The earliest upcoming fire instant
+    pub fn next_fire_time(&self) -> Option<DateTime<Utc>> {
+        self.queue.peek().map(|Reverse((fire_time, _))| *fire_time)
+    }
+
+    /// Remove and return every guild whose fire instant is at or before `now`,
+    /// queueing each guild's following post
+    pub fn pop_due(&mut self, servers: &HashMap<String, ServerConfig>, now: DateTime<Utc>) -> Vec<(String, DateTime<Utc>)> {
+        let mut due = Vec::new();
+
+        while let Some(Reverse((fire_time, _))) = self.queue.peek() {
+            if *fire_time > now {
+                break;
+            }
+            let Reverse((fire_time, guild_id)) = self.queue.pop().unwrap();
+            self.cursors.insert(guild_id.clone(), fire_time);
+
+            if let Some(next) = servers.get(&guild_id).and_then(|config| Self::next_fire(config, fire_time)) {
+                self.queue.push(Reverse((next, guild_id.clone())));
+            }
+
+            // A guild that fell behind by several posts only gets the latest one
+            due.retain(|(due_guild, _): &(String, DateTime<Utc>)| *due_guild != guild_id);
+            due.push((guild_id, fire_time));
+        }
+
+        due
+    }
+
+    fn next_fire(config: &ServerConfig, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let schedule = PostSchedule::for_server(config).ok()?;
+        let tz: Tz = config.timezone.parse().ok()?;
+        schedule.next_after(after, tz)
+    }
+}
+
+#[derive(Clone)]
 pub struct DailyScheduler {
     data: SharedBotData,
+    posting: Arc<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>>, // guild_id -> held while posting there
 }
 
 impl DailyScheduler {
     pub fn new(data: SharedBotData) -> Self {
-        Self { data }
+        Self { data, posting: Arc::default() }
     }
 
-    /// Start the daily scheduler loop
-    pub async fn start(&self, ctx: Context) {
+    /// Start the daily scheduler loop: sleep until the earliest scheduled post,
+    /// or until a server's configuration changes
+    pub async fn start(&self, platform: Arc<dyn Platform>) {
         info!("Starting daily scheduler");
-        
+
+        // Make up for posts missed while the bot was offline
+        self.catch_up_missed_posts(&*platform).await;
+
+        let mut fire_queue = FireQueue::default();
+        let (config_changed, clock) = {
+            let data = self.data.read().await;
+            fire_queue.rebuild(data.servers.values(), data.now());
+            (data.config_changed(), data.clock())
+        };
+
         loop {
-            // Check every 60 seconds if it's time to post
-            sleep(Duration::from_secs(60)).await;
-            
-            if let Err(e) = self.check_and_post_daily_messages(&ctx).await {
-                error!("Error in daily scheduler: {}", e);
-            }
-        }
-    }
+            let latest_wake = clock.now() + MAX_SLEEP;
+            let wake_at = fire_queue.next_fire_time().map_or(latest_wake, |fire_time| fire_time.min(latest_wake));
 
-    /// Check all servers and post daily messages if it's time
-    async fn check_and_post_daily_messages(&self, ctx: &Context) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut data = self.data.write().await;
-        let now = Utc::now();
-        
-        // Clone the servers map to avoid borrow checker issues
-        let servers = data.servers.clone();
-        
-        for (guild_id, server_config) in &servers {
-            // Skip if no channel configured
-            let channel_id = match &server_config.checkin_channel_id {
-                Some(id) => id,
-                None => {
-                    debug!("No checkin channel configured for guild {}", guild_id);
+            tokio::select! {
+                _ = clock.sleep_until(wake_at) => {}
+                _ = config_changed.notified() => {
+                    debug!("Server configuration changed, recomputing post schedule");
+                    let data = self.data.read().await;
+                    fire_queue.rebuild(data.servers.values(), data.now());
                     continue;
                 }
+            }
+
+            let due = {
+                let data = self.data.read().await;
+                fire_queue.pop_due(&data.servers, data.now())
             };
 
-            // Check if it's time to post for this server
-            if self.is_time_to_post(&server_config.daily_time, &server_config.timezone, now).await? {
-                // Check if we already posted recently
-                if self.already_posted_recently(&data, guild_id, now) {
-                    debug!("Already posted recently for guild {}", guild_id);
+            // Post concurrently so one slow guild doesn't delay the others
+            for (guild_id, fire_time) in due {
+                let scheduler = self.clone();
+                let platform = platform.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = scheduler.run_scheduled_post(&*platform, &guild_id, fire_time).await {
+                        error!("Error posting daily message for guild {}: {}", guild_id, e);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Check every guild for scheduled posts missed during downtime: excuse the days that
+    /// get no post, and make a late post for the most recent one where there's time left
+    async fn catch_up_missed_posts(&self, platform: &dyn Platform) {
+        let mut late_posts = Vec::new();
+
+        {
+            let mut data = self.data.write().await;
+            let now = data.now();
+            let servers: Vec<ServerConfig> = data.servers.values().cloned().collect();
+
+            for config in servers {
+                if config.checkin_channel_id.is_none() {
                     continue;
                 }
+                // Guilds that never had a post have nothing to catch up on
+                let Some(last_posted_at) = data.daily_posts.get(&config.guild_id).map(|post| post.posted_at) else {
+                    continue;
+                };
 
-                info!("Posting daily message for guild {} in channel {}", guild_id, channel_id);
-                
-                // Run streak maintenance inline
-                match StreakManager::reset_streaks_for_guild(&mut data, guild_id).await {
-                    Ok(reset_count) => {
-                        if reset_count > 0 {
-                            info!("Reset {} streaks for guild {} before daily post", reset_count, guild_id);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to run streak maintenance for guild {}: {}", guild_id, e);
-                    }
+                let plan = CatchUpPlan::for_guild(&config, last_posted_at, now);
+                for day in &plan.missed_days {
+                    info!("Bot missed the check-in post for {} in guild {}, excusing the day", day, config.guild_id);
+                    data.add_missed_post_day(config.guild_id.clone(), *day);
                 }
-                
-                // Save data after streak maintenance
-                if let Err(e) = data.save().await {
-                    error!("Failed to save data after streak maintenance for guild {}: {}", guild_id, e);
+                if let Some(catch_up) = plan.late_post {
+                    late_posts.push((config.guild_id.clone(), catch_up));
                 }
-                
-                let guild_id_parsed: GuildId = guild_id.parse()?;
-                let channel_id_parsed: ChannelId = channel_id.parse()?;
-                
-                // Release the write lock before posting
-                drop(data);
-                
-                self.post_daily_message(ctx, guild_id_parsed, channel_id_parsed).await?;
-                
-                // Re-acquire the write lock for the next iteration
-                data = self.data.write().await;
+            }
+
+            if let Err(e) = data.save().await {
+                error!("Failed to save missed post days: {}", e);
             }
         }
 
-        Ok(())
+        for (guild_id, catch_up) in late_posts {
+            info!("Making a late post for {} in guild {}", catch_up.missed_date, guild_id);
+            if let Err(e) = self.run_post(platform, &guild_id, catch_up.missed_at, Some(catch_up)).await {
+                error!("Error making late post for guild {}: {}", guild_id, e);
+            }
+        }
     }
 
-    /// Check if it's time to post based on server timezone and configured time
-    async fn is_time_to_post(
+    /// Run streak maintenance and post the daily message for one scheduled fire time
+    pub async fn run_scheduled_post(
         &self,
-        daily_time: &str,
-        timezone: &str,
-        now: DateTime<Utc>,
-    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-        // Parse the configured time
-        let target_time = NaiveTime::parse_from_str(daily_time, "%H:%M")?;
-        
-        // Parse the timezone
-        let tz: Tz = timezone.parse()?;
-        
-        // Convert current UTC time to server timezone
-        let local_now = now.with_timezone(&tz);
-        let local_time = local_now.time();
-        
-        // Check if current time matches target time (within 1 minute)
-        let target_minutes = target_time.hour() * 60 + target_time.minute();
-        let current_minutes = local_time.hour() * 60 + local_time.minute();
-        
-        Ok((current_minutes as i32 - target_minutes as i32).abs() < 1)
+        platform: &dyn Platform,
+        guild_id: &str,
+        fire_time: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.run_post(platform, guild_id, fire_time, None).await
     }
 
-    /// Check if we already posted recently for a guild (within last 20 hours to prevent double posting)
-    fn already_posted_recently(
+    /// Run streak maintenance and post the check-in scheduled at `fire_time`. If the post
+    /// can't be made, the day is recorded as missed by the bot so nobody's streak suffers.
+    async fn run_post(
+        &self,
+        platform: &dyn Platform,
+        guild_id: &str,
+        fire_time: DateTime<Utc>,
+        catch_up: Option<CatchUp>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // One post at a time per guild, so a second fire for the same time sees the
+        // first one's record below instead of racing it to Discord
+        let guild_lock = self.posting.lock().unwrap().entry(guild_id.to_string()).or_default().clone();
+        let _posting = guild_lock.lock().await;
+
+        let mut data = self.data.write().await;
+        // Check if we already posted for this scheduled time (e.g. before a restart)
+        if self.already_posted_since(&data, guild_id, fire_time) {
+            debug!("Already posted for {} in guild {}", fire_time, guild_id);
+            return Ok(());
+        }
+
+        // The day this post is for, in the server's timezone
+        let cycle_date = fire_time.with_timezone(&data.guild_timezone(guild_id)).date_naive();
+
+        // Skip if no channel configured
+        let channel_id = match data.get_server_config(guild_id).and_then(|config| config.checkin_channel_id.clone()) {
+            Some(id) => id,
+            None => {
+                debug!("No checkin channel configured for guild {}", guild_id);
+                return Ok(());
+            }
+        };
+
+        info!("Posting daily message for guild {} in channel {}", guild_id, channel_id);
+
+        // Run streak maintenance inline
+        match StreakManager::reset_streaks_for_guild(&mut data, guild_id, fire_time).await {
+            Ok(reset_count) => {
+                if reset_count > 0 {
+                    info!("Reset {} streaks for guild {} before daily post", reset_count, guild_id);
+                }
+            }
+            Err(e) => {
+                error!("Failed to run streak maintenance for guild {}: {}", guild_id, e);
+            }
+        }
+
+        // Save data after streak maintenance
+        if let Err(e) = data.save().await {
+            error!("Failed to save data after streak maintenance for guild {}: {}", guild_id, e);
+        }
+
+        let guild_id_parsed: GuildId = guild_id.parse()?;
+        let channel_id_parsed: ChannelId = channel_id.parse()?;
+
+        // Release the write lock before posting
+        drop(data);
+
+        if let Err(e) = self.post_daily_message(platform, guild_id_parsed, channel_id_parsed, cycle_date, catch_up).await {
+            let mut data = self.data.write().await;
+            data.add_missed_post_day(guild_id.to_string(), cycle_date);
+            if let Err(save_error) = data.save().await {
+                error!("Failed to save missed post day for guild {}: {}", guild_id, save_error);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Check if we already posted for a guild at or after the given scheduled time
+    fn already_posted_since(
         &self,
         data: &crate::data::BotData,
         guild_id: &str,
-        now: DateTime<Utc>,
+        fire_time: DateTime<Utc>,
     ) -> bool {
-        if let Some(post) = data.daily_posts.get(guild_id) {
-            let hours_since_post = now.signed_duration_since(post.posted_at).num_hours();
-            hours_since_post < 20 // Prevent posting again too soon
-        } else {
-            false
-        }
+        data.daily_posts
+            .get(guild_id)
+            .map(|post| post.posted_at >= fire_time)
+            .unwrap_or(false)
     }
 
-    /// Post the daily check-in message
+    /// Post the daily check-in message for `cycle_date`; users who paused that day are left out
     async fn post_daily_message(
         &self,
-        ctx: &Context,
+        platform: &dyn Platform,
         guild_id: GuildId,
         channel_id: ChannelId,
+        cycle_date: NaiveDate,
+        catch_up: Option<CatchUp>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Generate the daily message embed
-        let embed = self.generate_daily_embed(guild_id).await?;
+        let mut embed = self.generate_daily_embed(guild_id, cycle_date).await?;
+        if let Some(catch_up) = catch_up {
+            embed = embed.description(format!(
+                "Sorry, this check-in is late - the bot was offline! Update this thread with your progress for {} by <t:{}:f>.",
+                catch_up.missed_date.format("%m/%d/%y"),
+                catch_up.deadline.timestamp()
+            ));
+        }
         
         // Post the message
-        let message = channel_id.send_message(&ctx.http, CreateMessage::new().add_embed(embed)).await?;
+        let message = OutgoingMessage {
+            embed: Some(embed),
+            components: vec![checkin::button_row()],
+            ..OutgoingMessage::default()
+        };
+        let message_id = platform.send_message(channel_id, message).await?;
         
-        // Create a thread under the message with today's date
-        let today = Utc::now().format("%m/%d/%y");
-        let thread_name = format!("Daily Check-in Responses {}", today);
-        let thread = message
-            .channel_id
-            .create_thread(&ctx.http,
-                           CreateThread::new(thread_name).kind(serenity::model::channel::ChannelType::PublicThread)
-            ).await?;
+        // Create a thread for the responses with the date it is for
+        let late = if catch_up.is_some() { " (late)" } else { "" };
+        let thread_name = format!("Daily Check-in Responses {}{}", cycle_date.format("%m/%d/%y"), late);
+        let thread_id = platform.create_thread(channel_id, &thread_name).await?;
         
         // Send a ping message in the thread to notify all participants
-        self.send_thread_pings(ctx, thread.id, guild_id).await?;
+        self.send_thread_pings(platform, thread_id, guild_id, cycle_date).await?;
         
         // Save the daily post record
         {
             let mut data = self.data.write().await;
-            let now = Utc::now();
+            let now = data.now();
             let daily_post = DailyPost {
                 guild_id: guild_id.to_string(),
                 channel_id: channel_id.to_string(),
-                message_id: message.id.to_string(),
-                thread_id: Some(thread.id.to_string()),
+                message_id: message_id.to_string(),
+                thread_id: Some(thread_id.to_string()),
                 posted_at: now, // When the post was actually created
                 created_at: now,
+                catch_up_for: catch_up.map(|catch_up| catch_up.missed_date),
+                deadline: catch_up.map(|catch_up| catch_up.deadline),
             };
             
-            data.daily_posts.insert(guild_id.to_string(), daily_post);
+            data.set_daily_post(daily_post);
                 
             if let Err(e) = data.save().await {
                 error!("Failed to save daily post data: {}", e);
             }
         }
         
-        info!("Successfully posted daily message for guild {} with thread {}", guild_id, thread.id);
+        info!("Successfully posted daily message for guild {} with thread {}", guild_id, thread_id);
         Ok(())
     }
 
@@ -186,6 +407,7 @@ impl DailyScheduler {
     async fn generate_daily_embed(
         &self,
         guild_id: GuildId,
+        cycle_date: NaiveDate,
     ) -> Result<CreateEmbed, Box<dyn std::error::Error + Send + Sync>> {
         let data = self.data.read().await;
         let guild_id_str = guild_id.to_string();
@@ -194,8 +416,11 @@ impl DailyScheduler {
         let empty_map = std::collections::HashMap::new();
         let users = data.users.get(&guild_id_str).unwrap_or(&empty_map);
         
-        // Filter active users
-        let active_users: Vec<_> = users.values().filter(|user| user.is_active).collect();
+        // Filter active users who aren't paused or resting this day
+        let active_users: Vec<_> = users
+            .values()
+            .filter(|user| user.is_active && user.active_goals().next().is_some() && !user.is_away_on(cycle_date))
+            .collect();
         
         let mut embed = CreateEmbed::new()
             .title("Daily Check-in")
@@ -203,29 +428,47 @@ impl DailyScheduler {
             .color(0x00ff88); // Green color for daily check-ins
         
         if active_users.is_empty() {
-            embed = embed.field("No Users Registered", "Use `/register-goal` to join!", false);
+            embed = if users.values().any(|user| user.is_active) {
+                embed.field("Everyone's Taking a Break", "All participants are on a break or resting today.", false)
+            } else {
+                embed.field("No Users Registered", "Use `/register-goal` to join!", false)
+            };
             return Ok(embed);
         }
         
-        // Sort users by streak (highest first) for motivation
+        // Sort users by their best streak (highest first) for motivation
+        let best_streak = |user: &&UserData| user.active_goals().map(|goal| goal.current_streak).max().unwrap_or(0);
         let mut sorted_users = active_users;
-        sorted_users.sort_by(|a, b| b.current_streak.cmp(&a.current_streak));
+        sorted_users.sort_by_key(|user| std::cmp::Reverse(best_streak(user)));
         
-        // Build user list for the field
+        // Build user list for the field; users with several goals get a line per goal
         let mut user_list = String::new();
+        let mut any_multi_goal = false;
         for user in sorted_users {
             let user_mention = format!("<@{}>", user.user_id);
+            let goals: Vec<_> = user.active_goals().collect();
 
             // Truncate goal if it's too long for readability
-            let goal_display = if user.goal.len() > 50 {
-                format!("{}...", &user.goal[..47])
+            let goal_display = |description: &String| if description.len() > 50 {
+                format!("{}...", &description[..47])
             } else {
-                user.goal.clone()
+                description.clone()
             };
             
-            user_list.push_str(&format!("• {} - **{}** 🔥**{}**\n", user_mention, goal_display, user.current_streak));
+            if let [goal] = goals.as_slice() {
+                user_list.push_str(&format!("• {} - **{}** {}\n", user_mention, goal_display(&goal.description), streak_badge(goal, cycle_date)));
+            } else {
+                any_multi_goal = true;
+                user_list.push_str(&format!("• {}\n", user_mention));
+                for goal in goals {
+                    user_list.push_str(&format!("  ◦ `#{}` **{}** {}\n", goal.name, goal_display(&goal.description), streak_badge(goal, cycle_date)));
+                }
+            }
         }
         
+        if any_multi_goal {
+            embed = embed.description("Update this thread with today's progress! Tag a goal like `#name` to check in on just that one.");
+        }
         embed = embed
             .field("Participants", user_list, false);
 
@@ -235,9 +478,10 @@ impl DailyScheduler {
     /// Send ping message to thread to notify all participants
     async fn send_thread_pings(
         &self,
-        ctx: &Context,
-        thread_id: serenity::model::id::ChannelId,
+        platform: &dyn Platform,
+        thread_id: ChannelId,
         guild_id: GuildId,
+        cycle_date: NaiveDate,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let data = self.data.read().await;
         let guild_id_str = guild_id.to_string();
@@ -246,8 +490,11 @@ impl DailyScheduler {
         let empty_map = std::collections::HashMap::new();
         let users = data.users.get(&guild_id_str).unwrap_or(&empty_map);
         
-        // Filter active users and collect their mentions
-        let active_users: Vec<_> = users.values().filter(|user| user.is_active).collect();
+        // Filter active users who aren't paused or resting this day and collect their mentions
+        let active_users: Vec<_> = users
+            .values()
+            .filter(|user| user.is_active && user.active_goals().next().is_some() && !user.is_away_on(cycle_date))
+            .collect();
         
         if !active_users.is_empty() {
             let mentions: Vec<String> = active_users
@@ -258,9 +505,231 @@ impl DailyScheduler {
             let ping_message = format!("Time to check in!\n{}", mentions.join("\n"));
             
             // Send the ping message to the thread
-            thread_id.send_message(&ctx.http, CreateMessage::new().content(ping_message)).await?;
+            platform.send_message(thread_id, OutgoingMessage::text(ping_message)).await?;
         }
         
         Ok(())
     }
 }
+
+/// A goal's streak for the daily post; weekly goals show weeks and this week's progress
+fn streak_badge(goal: &Goal, cycle_date: NaiveDate) -> String {
+    match goal.frequency {
+        GoalFrequency::Daily => format!("🔥**{}**", goal.current_streak),
+        GoalFrequency::Weekly { times } => format!(
+            "🔥**{}** wk · {}/{} this week",
+            goal.current_streak,
+            goal.checkins_in_week_of(cycle_date),
+            times
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::FakeClock, data::BotData, platform::fake::FakePlatform};
+    use chrono::Duration as ChronoDuration;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn server(guild_id: &str, daily_time: &str) -> ServerConfig {
+        let mut config = ServerConfig::new(guild_id.to_string(), utc("2025-11-01T00:00:00Z"));
+        config.checkin_channel_id = Some("1".to_string());
+        config.daily_time = daily_time.to_string();
+        config
+    }
+
+    fn servers(configs: &[ServerConfig]) -> HashMap<String, ServerConfig> {
+        configs.iter().map(|config| (config.guild_id.clone(), config.clone())).collect()
+    }
+
+    #[test]
+    fn sleeps_until_the_earliest_guild() {
+        let configs = servers(&[server("a", "09:00"), server("b", "07:30")]);
+        let mut queue = FireQueue::default();
+        queue.rebuild(configs.values(), utc("2025-11-17T06:00:00Z"));
+
+        assert_eq!(queue.next_fire_time(), Some(utc("2025-11-17T07:30:00Z")));
+    }
+
+    #[test]
+    fn late_wake_ups_fire_exactly_once() {
+        let configs = servers(&[server("a", "09:00")]);
+        let mut queue = FireQueue::default();
+        queue.rebuild(configs.values(), utc("2025-11-17T08:00:00Z"));
+
+        // Woke up five minutes late, e.g. after a slow Discord call
+        let due = queue.pop_due(&configs, utc("2025-11-17T09:05:00Z"));
+        assert_eq!(due, vec![("a".to_string(), utc("2025-11-17T09:00:00Z"))]);
+
+        // Nothing is due again until tomorrow
+        assert!(queue.pop_due(&configs, utc("2025-11-17T09:06:00Z")).is_empty());
+        assert_eq!(queue.next_fire_time(), Some(utc("2025-11-18T09:00:00Z")));
+    }
+
+    #[test]
+    fn waking_early_fires_nothing() {
+        let configs = servers(&[server("a", "09:00")]);
+        let mut queue = FireQueue::default();
+        queue.rebuild(configs.values(), utc("2025-11-17T08:00:00Z"));
+
+        assert!(queue.pop_due(&configs, utc("2025-11-17T08:59:59Z")).is_empty());
+        assert_eq!(queue.pop_due(&configs, utc("2025-11-17T09:00:00Z")).len(), 1);
+    }
+
+    #[test]
+    fn rebuilding_does_not_repeat_a_fired_post() {
+        let configs = servers(&[server("a", "09:00")]);
+        let mut queue = FireQueue::default();
+        queue.rebuild(configs.values(), utc("2025-11-17T08:00:00Z"));
+        queue.pop_due(&configs, utc("2025-11-17T09:00:01Z"));
+
+        // Another guild's config changes right after the post
+        queue.rebuild(configs.values(), utc("2025-11-17T09:00:02Z"));
+        assert_eq!(queue.next_fire_time(), Some(utc("2025-11-18T09:00:00Z")));
+    }
+
+    #[test]
+    fn changed_configs_only_schedule_from_now_on() {
+        let mut config = server("a", "09:00");
+        let mut queue = FireQueue::default();
+        queue.rebuild([&config], utc("2025-11-17T08:00:00Z"));
+
+        // At 10:00 the admin moves the post to 08:00; today's 08:00 is already past
+        config.daily_time = "08:00".to_string();
+        config.updated_at = utc("2025-11-17T10:00:00Z");
+        queue.rebuild([&config], utc("2025-11-17T10:00:00Z"));
+        assert_eq!(queue.next_fire_time(), Some(utc("2025-11-18T08:00:00Z")));
+    }
+
+    #[test]
+    fn guilds_without_a_channel_are_not_scheduled() {
+        let mut config = server("a", "09:00");
+        config.checkin_channel_id = None;
+        let mut queue = FireQueue::default();
+        queue.rebuild([&config], utc("2025-11-17T08:00:00Z"));
+        assert_eq!(queue.next_fire_time(), None);
+    }
+
+    #[test]
+    fn guilds_far_behind_only_get_the_latest_post() {
+        let mut config = server("a", "09:00");
+        config.cron_schedule = Some("0 * * * *".to_string());
+        let configs = servers(&[config]);
+        let mut queue = FireQueue::default();
+        queue.rebuild(configs.values(), utc("2025-11-17T08:30:00Z"));
+
+        let now = utc("2025-11-17T08:30:00Z") + ChronoDuration::hours(3);
+        let due = queue.pop_due(&configs, now);
+        assert_eq!(due, vec![("a".to_string(), utc("2025-11-17T11:00:00Z"))]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_fires_post_once() {
+        let mut data = BotData::default();
+        data.servers.insert("1000".to_string(), server("1000", "09:00"));
+        let data: SharedBotData = Arc::new(tokio::sync::RwLock::new(data));
+        let scheduler = DailyScheduler::new(data.clone());
+        let platform = Arc::new(FakePlatform::default());
+        let fire_time = utc("2025-11-17T09:00:00Z");
+
+        // Both fires queue up behind a command holding the data
+        let held = data.write().await;
+        let fires: Vec<_> = (0..2)
+            .map(|_| {
+                let (scheduler, platform) = (scheduler.clone(), platform.clone());
+                tokio::spawn(async move { scheduler.run_scheduled_post(&*platform, "1000", fire_time).await.unwrap() })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+        drop(held);
+        for fire in fires {
+            fire.await.unwrap();
+        }
+
+        assert_eq!(platform.sent_to(ChannelId::new(1)).len(), 1);
+        assert_eq!(platform.threads().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn posts_when_the_clock_reaches_the_fire_time() {
+        let clock = FakeClock::new(utc("2025-11-17T08:00:00Z"));
+        let mut data = BotData::default();
+        data.set_clock(clock.clone());
+        data.servers.insert("1000".to_string(), server("1000", "09:00"));
+        let scheduler = DailyScheduler::new(Arc::new(tokio::sync::RwLock::new(data)));
+        let platform = Arc::new(FakePlatform::default());
+        let running = tokio::spawn({
+            let (scheduler, platform) = (scheduler.clone(), platform.clone());
+            async move { scheduler.start(platform).await }
+        });
+
+        let settle = || async {
+            for _ in 0..100 {
+                tokio::task::yield_now().await;
+            }
+        };
+        settle().await;
+        clock.set(utc("2025-11-17T08:59:59Z"));
+        settle().await;
+        assert!(platform.sent_to(ChannelId::new(1)).is_empty());
+
+        clock.set(utc("2025-11-17T09:00:01Z"));
+        settle().await;
+        assert_eq!(platform.sent_to(ChannelId::new(1)).len(), 1);
+        assert_eq!(scheduler.data.read().await.daily_posts["1000"].posted_at, utc("2025-11-17T09:00:01Z"));
+        running.abort();
+    }
+
+    #[test]
+    fn no_downtime_means_no_catch_up() {
+        let config = server("a", "09:00");
+        let plan = CatchUpPlan::for_guild(&config, utc("2025-11-17T09:00:05Z"), utc("2025-11-17T20:00:00Z"));
+        assert_eq!(plan, CatchUpPlan::default());
+    }
+
+    #[test]
+    fn missed_post_is_made_up_until_the_next_regular_post() {
+        let config = server("a", "09:00");
+        // Last posted Monday morning, offline from Monday evening until Tuesday 15:00
+        let plan = CatchUpPlan::for_guild(&config, utc("2025-11-17T09:00:05Z"), utc("2025-11-18T15:00:00Z"));
+        assert_eq!(plan.late_post, Some(CatchUp {
+            missed_at: utc("2025-11-18T09:00:00Z"),
+            missed_date: NaiveDate::from_ymd_opt(2025, 11, 18).unwrap(),
+            deadline: utc("2025-11-19T09:00:00Z"),
+        }));
+        assert!(plan.missed_days.is_empty());
+    }
+
+    #[test]
+    fn older_missed_posts_are_excused() {
+        let config = server("a", "09:00");
+        let plan = CatchUpPlan::for_guild(&config, utc("2025-11-14T09:00:05Z"), utc("2025-11-17T12:00:00Z"));
+        assert_eq!(plan.late_post.map(|catch_up| catch_up.missed_date), NaiveDate::from_ymd_opt(2025, 11, 17));
+        assert_eq!(plan.missed_days, vec![
+            NaiveDate::from_ymd_opt(2025, 11, 15).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 11, 16).unwrap(),
+        ]);
+    }
+
+    #[test]
+    fn no_late_post_right_before_the_next_regular_post() {
+        let config = server("a", "09:00");
+        let plan = CatchUpPlan::for_guild(&config, utc("2025-11-16T09:00:05Z"), utc("2025-11-18T08:30:00Z"));
+        assert_eq!(plan.late_post, None);
+        assert_eq!(plan.missed_days, vec![NaiveDate::from_ymd_opt(2025, 11, 17).unwrap()]);
+    }
+
+    #[test]
+    fn missed_days_are_dates_in_the_server_timezone() {
+        // 08:00 in Tokyo is 23:00 UTC the day before
+        let mut config = server("a", "08:00");
+        config.timezone = "Asia/Tokyo".to_string();
+        let plan = CatchUpPlan::for_guild(&config, utc("2025-11-15T23:00:05Z"), utc("2025-11-18T01:00:00Z"));
+        assert_eq!(plan.late_post.map(|catch_up| catch_up.missed_date), NaiveDate::from_ymd_opt(2025, 11, 18));
+        assert_eq!(plan.missed_days, vec![NaiveDate::from_ymd_opt(2025, 11, 17).unwrap()]);
+    }
+}
@@ -0,0 +1,357 @@
+use super::{PendingChanges, Storage};
+use crate::data::BotData;
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDateTime, Utc};
+use serenity::async_trait;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tokio::{fs, io::AsyncWriteExt};
+use tracing::{error, warn};
+
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+pub fn data_file_path() -> String {
+    std::env::var("DATA_FILE_PATH").unwrap_or_else(|_| "bot_data.json".to_string())
+}
+
+/// Stores the whole `BotData` as one pretty-printed JSON document.
+///
+/// Saves go to a temporary file that is fsynced and then renamed over the data
+/// file, so a crash mid-write leaves the previous version intact. Before the
+/// data file is replaced, a timestamped copy of it is kept as a backup (at most
+/// one per `backup_interval`, keeping the newest `backup_count`).
+#[derive(Debug)]
+pub struct JsonStorage {
+    path: PathBuf,
+    backup_count: usize,
+    backup_interval: Duration,
+}
+
+impl JsonStorage {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let backup_count = std::env::var("DATA_BACKUP_COUNT")
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(5);
+
+        Self {
+            path: path.into(),
+            backup_count,
+            backup_interval: Duration::hours(1),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn exists(&self) -> bool {
+        fs::try_exists(&self.path).await.unwrap_or(false)
+    }
+
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "bot_data.json".to_string())
+    }
+
+    fn parent_dir(&self) -> PathBuf {
+        match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+
+    fn backup_prefix(&self) -> String {
+        format!("{}.backup-", self.file_name())
+    }
+
+    /// Existing backups with their timestamps, newest first
+    async fn list_backups(&self) -> Result<Vec<(NaiveDateTime, PathBuf)>> {
+        let prefix = self.backup_prefix();
+        let mut backups = Vec::new();
+
+        let mut entries = fs::read_dir(self.parent_dir()).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(timestamp) = name.strip_prefix(&prefix) {
+                if let Ok(taken_at) = NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT) {
+                    backups.push((taken_at, entry.path()));
+                }
+            }
+        }
+
+        backups.sort_by_key(|(taken_at, _)| std::cmp::Reverse(*taken_at));
+        Ok(backups)
+    }
+
+    /// Copy the current data file to a new backup if the newest one is old enough,
+    /// then delete backups beyond `backup_count`
+    async fn rotate_backups(&self) -> Result<()> {
+        if self.backup_count == 0 || !self.exists().await {
+            return Ok(());
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut backups = self.list_backups().await?;
+
+        let due = backups
+            .first()
+            .map(|(taken_at, _)| now.signed_duration_since(*taken_at) >= self.backup_interval)
+            .unwrap_or(true);
+        if due {
+            let backup_path = self.parent_dir().join(format!(
+                "{}{}",
+                self.backup_prefix(),
+                now.format(BACKUP_TIMESTAMP_FORMAT)
+            ));
+            fs::copy(&self.path, &backup_path).await?;
+            backups.insert(0, (now, backup_path));
+        }
+
+        for (_, stale) in backups.iter().skip(self.backup_count) {
+            if let Err(e) = fs::remove_file(stale).await {
+                warn!("Failed to remove old backup {}: {}", stale.display(), e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fall back to the newest backup that still parses
+    async fn load_newest_valid_backup(&self) -> Result<Option<BotData>> {
+        for (_, backup_path) in self.list_backups().await? {
+            let parsed = fs::read_to_string(&backup_path)
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|content| serde_json::from_str(&content).map_err(Into::into))
+                .and_then(BotData::from_document);
+
+            match parsed {
+                Ok(data) => {
+                    warn!("Recovered bot data from backup {}", backup_path.display());
+                    return Ok(Some(data));
+                }
+                Err(e) => warn!("Backup {} is not usable either: {}", backup_path.display(), e),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl Storage for JsonStorage {
+    async fn load(&self) -> Result<BotData> {
+        let content = match fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                // No data file yet, start fresh
+                return Ok(BotData::default());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.path.display()));
+            }
+        };
+
+        match serde_json::from_str(&content) {
+            // Migration or shape errors are not corruption, so they never fall back to a backup
+            Ok(document) => BotData::from_document(document),
+            Err(parse_error) => {
+                // Never silently reset: either recover from a backup or refuse to start
+                error!("Data file {} is corrupt: {}", self.path.display(), parse_error);
+                match self.load_newest_valid_backup().await? {
+                    Some(data) => {
+                        // Keep the corrupt file for inspection, out of the way of backup rotation
+                        let corrupt_path = self.parent_dir().join(format!(
+                            "{}.corrupt-{}",
+                            self.file_name(),
+                            Utc::now().format(BACKUP_TIMESTAMP_FORMAT)
+                        ));
+                        fs::rename(&self.path, &corrupt_path).await?;
+                        warn!("Moved corrupt data file to {}", corrupt_path.display());
+                        Ok(data)
+                    }
+                    None => Err(anyhow::anyhow!(
+                        "Data file {} could not be parsed and no valid backup was found: {}",
+                        self.path.display(),
+                        parse_error
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn save(&self, data: &BotData, _changes: &PendingChanges) -> Result<()> {
+        // The document is always rewritten as a whole
+        let content = serde_json::to_string_pretty(data)?;
+
+        if let Err(e) = self.rotate_backups().await {
+            warn!("Failed to back up {}: {}", self.path.display(), e);
+        }
+
+        // Write to a temporary file next to the data file, then atomically replace it
+        let tmp_path = self.parent_dir().join(format!(".{}.tmp", self.file_name()));
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.path).await?;
+
+        // Persist the rename itself; not every platform supports syncing directories
+        if let Ok(dir) = fs::File::open(self.parent_dir()).await {
+            let _ = dir.sync_all().await;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::ServerConfig, storage::TempDir};
+
+    fn storage(dir: &TempDir, backup_count: usize) -> JsonStorage {
+        JsonStorage {
+            path: dir.path().join("bot_data.json"),
+            backup_count,
+            backup_interval: Duration::hours(1),
+        }
+    }
+
+    /// Data with a server per id, so versions can be told apart
+    fn data_with_servers(guild_ids: &[&str]) -> BotData {
+        let mut data = BotData::default();
+        for guild_id in guild_ids {
+            data.add_or_update_server(ServerConfig::new(guild_id.to_string(), Utc::now()));
+        }
+        data
+    }
+
+    fn guild_ids(data: &BotData) -> Vec<String> {
+        let mut guild_ids: Vec<String> = data.servers.keys().cloned().collect();
+        guild_ids.sort();
+        guild_ids
+    }
+
+    /// Write a backup as if it had been taken `hours_ago`
+    fn write_backup(storage: &JsonStorage, hours_ago: i64, content: &str) -> PathBuf {
+        let taken_at = Utc::now().naive_utc() - Duration::hours(hours_ago);
+        let path = storage.parent_dir().join(format!(
+            "{}{}",
+            storage.backup_prefix(),
+            taken_at.format(BACKUP_TIMESTAMP_FORMAT)
+        ));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn files_in(dir: &TempDir) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn missing_file_loads_as_empty_but_unreadable_file_is_an_error() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 5);
+        assert!(storage.load().await.unwrap().servers.is_empty());
+
+        // Anything other than a missing file must not reset the data
+        std::fs::create_dir(&storage.path).unwrap();
+        assert!(storage.load().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn saves_replace_the_file_through_a_temporary_one() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 5);
+
+        // A temporary file left behind by a crash mid-write is neither loaded nor kept
+        let tmp_path = dir.path().join(".bot_data.json.tmp");
+        std::fs::write(&tmp_path, "{ \"servers\": ").unwrap();
+        storage.save(&data_with_servers(&["1"]), &PendingChanges::default()).await.unwrap();
+        assert!(!tmp_path.exists());
+        assert_eq!(guild_ids(&storage.load().await.unwrap()), ["1"]);
+
+        storage.save(&data_with_servers(&["1", "2"]), &PendingChanges::default()).await.unwrap();
+        assert!(!tmp_path.exists());
+        assert_eq!(guild_ids(&storage.load().await.unwrap()), ["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn saves_back_up_the_previous_file_at_most_hourly() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 5);
+
+        // Nothing to back up on the first save
+        storage.save(&data_with_servers(&["1"]), &PendingChanges::default()).await.unwrap();
+        assert!(storage.list_backups().await.unwrap().is_empty());
+
+        storage.save(&data_with_servers(&["1", "2"]), &PendingChanges::default()).await.unwrap();
+        storage.save(&data_with_servers(&["1", "2", "3"]), &PendingChanges::default()).await.unwrap();
+        let backups = storage.list_backups().await.unwrap();
+        assert_eq!(backups.len(), 1);
+        let backup = std::fs::read_to_string(&backups[0].1).unwrap();
+        assert_eq!(guild_ids(&serde_json::from_str(&backup).unwrap()), ["1"]);
+    }
+
+    #[tokio::test]
+    async fn old_backups_are_pruned_to_the_backup_count() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 3);
+        let oldest: Vec<PathBuf> = (2..=6).rev().map(|hours_ago| write_backup(&storage, hours_ago, "{}")).collect();
+        let kept = write_backup(&storage, 1, "{}");
+        storage.save(&data_with_servers(&["1"]), &PendingChanges::default()).await.unwrap();
+        storage.save(&data_with_servers(&["2"]), &PendingChanges::default()).await.unwrap();
+
+        let backups: Vec<PathBuf> = storage.list_backups().await.unwrap().into_iter().map(|(_, path)| path).collect();
+        assert_eq!(backups.len(), 3);
+        assert_eq!(backups[1], kept);
+        assert_eq!(backups[2], oldest[4]);
+        assert!(oldest[..4].iter().all(|path| !path.exists()));
+    }
+
+    #[tokio::test]
+    async fn corrupt_file_falls_back_to_the_newest_valid_backup() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 5);
+        let document = |guild_ids: &[&str]| serde_json::to_string(&data_with_servers(guild_ids)).unwrap();
+        write_backup(&storage, 3, &document(&["old"]));
+        write_backup(&storage, 2, &document(&["newer"]));
+        write_backup(&storage, 1, "{ \"servers\": {");
+        std::fs::write(&storage.path, "not json").unwrap();
+
+        assert_eq!(guild_ids(&storage.load().await.unwrap()), ["newer"]);
+
+        // The corrupt file is kept for inspection, under a name that isn't a backup
+        assert!(!storage.path.exists());
+        let corrupt: Vec<String> = files_in(&dir)
+            .into_iter()
+            .filter(|name| name.starts_with("bot_data.json.corrupt-"))
+            .collect();
+        assert_eq!(corrupt.len(), 1);
+        assert_eq!(std::fs::read_to_string(dir.path().join(&corrupt[0])).unwrap(), "not json");
+        assert_eq!(storage.list_backups().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn corrupt_file_without_a_valid_backup_refuses_to_load() {
+        let dir = TempDir::new();
+        let storage = storage(&dir, 5);
+        write_backup(&storage, 1, "truncated {");
+        std::fs::write(&storage.path, "not json").unwrap();
+
+        let error = storage.load().await.unwrap_err();
+        assert!(error.to_string().contains("no valid backup"));
+        // Nothing is moved or reset, so the data can still be repaired by hand
+        assert_eq!(std::fs::read_to_string(&storage.path).unwrap(), "not json");
+    }
+}
@@ -0,0 +1,104 @@
+pub mod json;
+pub mod sqlite;
+
+use crate::data::BotData;
+use anyhow::Result;
+use serenity::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+pub use json::JsonStorage;
+pub use sqlite::SqliteStorage;
+
+/// Records which parts of `BotData` were touched since the last save, so
+/// backends that store rows individually only have to write what changed.
+#[derive(Debug, Default)]
+pub struct PendingChanges {
+    pub users: HashSet<(String, String)>, // (guild_id, user_id)
+    pub servers: HashSet<String>,
+    pub daily_posts: HashSet<String>,
+    pub new_checkins: HashMap<String, usize>, // guild_id -> records appended at the end of the log
+    pub rewritten_checkins: HashSet<String>, // guilds whose log changed other than by appending
+    pub missed_post_days: HashSet<String>,
+    pub everything: bool, // rewrite all records, e.g. after a schema migration
+}
+
+impl PendingChanges {
+    pub fn is_empty(&self) -> bool {
+        !self.everything
+            && self.users.is_empty()
+            && self.servers.is_empty()
+            && self.daily_posts.is_empty()
+            && self.new_checkins.is_empty()
+            && self.rewritten_checkins.is_empty()
+            && self.missed_post_days.is_empty()
+    }
+}
+
+/// A persistence backend for `BotData`.
+///
+/// The bot keeps the whole data set in memory; a backend only has to load it
+/// once on startup and persist the changes made by `BotData`'s operations
+/// (`add_or_update_user`, `add_or_update_server`, `set_daily_post`, ...).
+#[async_trait]
+pub trait Storage: std::fmt::Debug + Send + Sync {
+    /// Load the stored data, or an empty `BotData` if nothing was stored yet
+    async fn load(&self) -> Result<BotData>;
+
+    /// Persist `data`, given the changes made since the last save
+    async fn save(&self, data: &BotData, changes: &PendingChanges) -> Result<()>;
+}
+
+/// Build the storage backend selected by the `STORAGE_BACKEND` environment variable.
+///
+/// * `json` (default) - a single JSON document at `DATA_FILE_PATH`
+/// * `sqlite` - an embedded SQLite database at `SQLITE_PATH`; an existing JSON
+///   data file is imported once when the database is still empty
+pub async fn from_env() -> Result<Arc<dyn Storage>> {
+    let backend = std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "json".to_string());
+
+    match backend.to_lowercase().as_str() {
+        "json" => {
+            let storage = JsonStorage::new(json::data_file_path());
+            info!("Using JSON storage at {}", storage.path().display());
+            Ok(Arc::new(storage))
+        }
+        "sqlite" => {
+            let path = std::env::var("SQLITE_PATH").unwrap_or_else(|_| "bot_data.db".to_string());
+            let storage = SqliteStorage::open(&path)?;
+            info!("Using SQLite storage at {}", path);
+            storage.migrate_from_json(&JsonStorage::new(json::data_file_path())).await?;
+            Ok(Arc::new(storage))
+        }
+        other => Err(anyhow::anyhow!(
+            "Unknown STORAGE_BACKEND '{}', expected 'json' or 'sqlite'",
+            other
+        )),
+    }
+}
+
+/// A fresh directory under the system temp dir for storage tests, removed again on drop
+#[cfg(test)]
+#[derive(Debug)]
+pub struct TempDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("daily-checkin-bot-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,568 @@
+use super::{JsonStorage, PendingChanges, Storage};
+use crate::{
+    data::{BotData, CheckinRecord, UserData},
+    migrations::CURRENT_SCHEMA_VERSION,
+};
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{Map, Value};
+use serenity::async_trait;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+// Each row keeps the serialized struct in `data`, with the lookup keys pulled
+// out into their own columns. New struct fields therefore need no schema change.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS servers (
+        guild_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS users (
+        guild_id TEXT NOT NULL,
+        user_id TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (guild_id, user_id)
+    );
+    CREATE TABLE IF NOT EXISTS daily_posts (
+        guild_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS checkins (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        guild_id TEXT NOT NULL,
+        user_id TEXT NOT NULL,
+        checkin_date TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS checkins_by_user ON checkins (guild_id, user_id, checkin_date);
+    CREATE TABLE IF NOT EXISTS missed_post_days (
+        guild_id TEXT NOT NULL,
+        day TEXT NOT NULL,
+        PRIMARY KEY (guild_id, day)
+    );
+";
+
+/// Stores `BotData` in an embedded SQLite database, one row per record.
+///
+/// rusqlite blocks, so every query runs on tokio's blocking thread pool rather than
+/// on a runtime worker. Callers save while holding the `BotData` write lock, so the
+/// records to write are serialized up front and only the rows cross over.
+#[derive(Debug)]
+pub struct SqliteStorage {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        // Rows only lack a version if they predate versioning, so a new database
+        // starts at the current one; incremental saves never write it
+        if is_empty(&conn)? {
+            store_schema_version(&conn, CURRENT_SCHEMA_VERSION)?;
+        }
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    /// Run `query` against the connection on the blocking thread pool
+    async fn run<T: Send + 'static>(
+        &self,
+        query: impl FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || query(&mut conn.lock().unwrap())).await?
+    }
+
+    /// Import an existing JSON data file, but only into an empty database
+    pub async fn migrate_from_json(&self, json: &JsonStorage) -> Result<()> {
+        if !json.exists().await {
+            return Ok(());
+        }
+        if !self.run(|conn| is_empty(conn)).await? {
+            info!(
+                "SQLite database already has data, not importing JSON data file {}",
+                json.path().display()
+            );
+            return Ok(());
+        }
+
+        let data = json.load().await?;
+        let rows = Rows::all(&data)?;
+        self.run(move |conn| rows.write(conn)).await?;
+        info!(
+            "Migrated {} servers and {} guild user lists from {} into SQLite",
+            data.servers.len(),
+            data.users.len(),
+            json.path().display()
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn load(&self) -> Result<BotData> {
+        match self.run(load_document).await? {
+            Some(document) => BotData::from_document(document),
+            None => Ok(BotData::default()),
+        }
+    }
+
+    async fn save(&self, data: &BotData, changes: &PendingChanges) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let rows = if changes.everything {
+            Rows::all(data)?
+        } else {
+            Rows::changed(data, changes)?
+        };
+        self.run(move |conn| rows.write(conn)).await
+    }
+}
+
+fn store_schema_version(conn: &Connection, version: u32) -> Result<()> {
+    conn.execute(
+        "INSERT INTO meta (key, value) VALUES ('schema_version', ?1)
+         ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+        params![version.to_string()],
+    )?;
+    Ok(())
+}
+
+fn is_empty(conn: &Connection) -> Result<bool> {
+    let has_rows: Option<i64> = conn
+        .query_row(
+            "SELECT 1 FROM servers UNION ALL SELECT 1 FROM users LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(has_rows.is_none())
+}
+
+/// Assemble the same document shape the JSON backend stores, so both go through the
+/// same schema migrations. Rows from before versioning count as version 0.
+/// Returns `None` for an empty database.
+fn load_document(conn: &mut Connection) -> Result<Option<Value>> {
+    if is_empty(conn)? {
+        return Ok(None);
+    }
+    let mut document = Map::new();
+
+    let version: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = 'schema_version'", [], |row| row.get(0))
+        .optional()?;
+    if let Some(version) = version {
+        document.insert("schema_version".to_string(), Value::from(version.parse::<u32>()?));
+    }
+
+    let mut servers = Map::new();
+    let mut stmt = conn.prepare("SELECT guild_id, data FROM servers")?;
+    for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+        let (guild_id, json) = row?;
+        servers.insert(guild_id, serde_json::from_str(&json)?);
+    }
+    document.insert("servers".to_string(), Value::Object(servers));
+
+    let mut users = Map::new();
+    let mut stmt = conn.prepare("SELECT guild_id, user_id, data FROM users")?;
+    for row in stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+    })? {
+        let (guild_id, user_id, json) = row?;
+        if let Value::Object(guild_users) = users
+            .entry(guild_id)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            guild_users.insert(user_id, serde_json::from_str(&json)?);
+        }
+    }
+    document.insert("users".to_string(), Value::Object(users));
+
+    let mut daily_posts = Map::new();
+    let mut stmt = conn.prepare("SELECT guild_id, data FROM daily_posts")?;
+    for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+        let (guild_id, json) = row?;
+        daily_posts.insert(guild_id, serde_json::from_str(&json)?);
+    }
+    document.insert("daily_posts".to_string(), Value::Object(daily_posts));
+
+    let mut checkins = Map::new();
+    let mut stmt = conn.prepare("SELECT guild_id, data FROM checkins ORDER BY id")?;
+    for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+        let (guild_id, json) = row?;
+        if let Value::Array(records) = checkins
+            .entry(guild_id)
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            records.push(serde_json::from_str(&json)?);
+        }
+    }
+    document.insert("checkins".to_string(), Value::Object(checkins));
+
+    let mut missed_post_days = Map::new();
+    let mut stmt = conn.prepare("SELECT guild_id, day FROM missed_post_days ORDER BY day")?;
+    for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+        let (guild_id, day) = row?;
+        if let Value::Array(days) = missed_post_days
+            .entry(guild_id)
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            days.push(Value::String(day));
+        }
+    }
+    document.insert("missed_post_days".to_string(), Value::Object(missed_post_days));
+
+    Ok(Some(Value::Object(document)))
+}
+
+/// Serialized records for one save, written in a single transaction.
+/// A `None` row deletes the record.
+#[derive(Debug, Default)]
+struct Rows {
+    replace_all: Option<u32>, // schema version to store after deleting every record
+    servers: Vec<(String, Option<String>)>, // (guild_id, data)
+    users: Vec<(String, String, Option<String>)>, // (guild_id, user_id, data)
+    daily_posts: Vec<(String, Option<String>)>, // (guild_id, data)
+    missed_post_days: Vec<(String, Vec<String>)>, // guild_id -> all of its days
+    cleared_checkins: Vec<String>, // guilds whose check-in log is deleted before inserting
+    checkins: Vec<(String, String, String, String)>, // (guild_id, user_id, checkin_date, data)
+}
+
+impl Rows {
+    /// Every record in `data`, replacing whatever is stored
+    fn all(data: &BotData) -> Result<Self> {
+        let mut rows = Self {
+            replace_all: Some(data.schema_version),
+            ..Self::default()
+        };
+        for server in data.servers.values() {
+            rows.servers.push((server.guild_id.clone(), Some(serde_json::to_string(server)?)));
+        }
+        for (guild_id, guild_users) in &data.users {
+            for user in guild_users.values() {
+                rows.add_user(guild_id, user)?;
+            }
+        }
+        for post in data.daily_posts.values() {
+            rows.daily_posts.push((post.guild_id.clone(), Some(serde_json::to_string(post)?)));
+        }
+        for (guild_id, records) in &data.checkins {
+            for record in records {
+                rows.add_checkin(guild_id, record)?;
+            }
+        }
+        for guild_id in data.missed_post_days.keys() {
+            rows.add_missed_post_days(data, guild_id);
+        }
+        Ok(rows)
+    }
+
+    /// The records named in `changes`
+    fn changed(data: &BotData, changes: &PendingChanges) -> Result<Self> {
+        let mut rows = Self::default();
+        for guild_id in &changes.servers {
+            let server = data.servers.get(guild_id).map(serde_json::to_string).transpose()?;
+            rows.servers.push((guild_id.clone(), server));
+        }
+        for (guild_id, user_id) in &changes.users {
+            match data.get_user(guild_id, user_id) {
+                Some(user) => rows.add_user(guild_id, user)?,
+                None => rows.users.push((guild_id.clone(), user_id.clone(), None)),
+            }
+        }
+        for guild_id in &changes.daily_posts {
+            let post = data.daily_posts.get(guild_id).map(serde_json::to_string).transpose()?;
+            rows.daily_posts.push((guild_id.clone(), post));
+        }
+        for guild_id in &changes.missed_post_days {
+            rows.add_missed_post_days(data, guild_id);
+        }
+        for guild_id in &changes.rewritten_checkins {
+            rows.cleared_checkins.push(guild_id.clone());
+            for record in data.get_checkins(guild_id) {
+                rows.add_checkin(guild_id, record)?;
+            }
+        }
+        for (guild_id, count) in &changes.new_checkins {
+            let records = data.get_checkins(guild_id);
+            for record in &records[records.len().saturating_sub(*count)..] {
+                rows.add_checkin(guild_id, record)?;
+            }
+        }
+        Ok(rows)
+    }
+
+    fn add_user(&mut self, guild_id: &str, user: &UserData) -> Result<()> {
+        self.users.push((guild_id.to_string(), user.user_id.clone(), Some(serde_json::to_string(user)?)));
+        Ok(())
+    }
+
+    fn add_checkin(&mut self, guild_id: &str, record: &CheckinRecord) -> Result<()> {
+        self.checkins.push((
+            guild_id.to_string(),
+            record.user_id.clone(),
+            record.checkin_date.to_string(),
+            serde_json::to_string(record)?,
+        ));
+        Ok(())
+    }
+
+    fn add_missed_post_days(&mut self, data: &BotData, guild_id: &str) {
+        let days = data.missed_post_days.get(guild_id).into_iter().flatten();
+        self.missed_post_days
+            .push((guild_id.to_string(), days.map(|day| day.to_string()).collect()));
+    }
+
+    fn write(self, conn: &mut Connection) -> Result<()> {
+        let tx = conn.transaction()?;
+
+        if let Some(schema_version) = self.replace_all {
+            tx.execute_batch(
+                "DELETE FROM servers; DELETE FROM users; DELETE FROM daily_posts; DELETE FROM checkins;
+                 DELETE FROM missed_post_days;",
+            )?;
+            store_schema_version(&tx, schema_version)?;
+        }
+
+        for (guild_id, data) in self.servers {
+            match data {
+                Some(data) => tx.execute(
+                    "INSERT INTO servers (guild_id, data) VALUES (?1, ?2)
+                     ON CONFLICT (guild_id) DO UPDATE SET data = excluded.data",
+                    params![guild_id, data],
+                )?,
+                None => tx.execute("DELETE FROM servers WHERE guild_id = ?1", params![guild_id])?,
+            };
+        }
+
+        for (guild_id, user_id, data) in self.users {
+            match data {
+                Some(data) => tx.execute(
+                    "INSERT INTO users (guild_id, user_id, data) VALUES (?1, ?2, ?3)
+                     ON CONFLICT (guild_id, user_id) DO UPDATE SET data = excluded.data",
+                    params![guild_id, user_id, data],
+                )?,
+                None => tx.execute(
+                    "DELETE FROM users WHERE guild_id = ?1 AND user_id = ?2",
+                    params![guild_id, user_id],
+                )?,
+            };
+        }
+
+        for (guild_id, data) in self.daily_posts {
+            match data {
+                Some(data) => tx.execute(
+                    "INSERT INTO daily_posts (guild_id, data) VALUES (?1, ?2)
+                     ON CONFLICT (guild_id) DO UPDATE SET data = excluded.data",
+                    params![guild_id, data],
+                )?,
+                None => tx.execute("DELETE FROM daily_posts WHERE guild_id = ?1", params![guild_id])?,
+            };
+        }
+
+        for (guild_id, days) in self.missed_post_days {
+            tx.execute("DELETE FROM missed_post_days WHERE guild_id = ?1", params![guild_id])?;
+            for day in days {
+                tx.execute(
+                    "INSERT INTO missed_post_days (guild_id, day) VALUES (?1, ?2)",
+                    params![guild_id, day],
+                )?;
+            }
+        }
+
+        for guild_id in self.cleared_checkins {
+            tx.execute("DELETE FROM checkins WHERE guild_id = ?1", params![guild_id])?;
+        }
+        for (guild_id, user_id, checkin_date, data) in self.checkins {
+            tx.execute(
+                "INSERT INTO checkins (guild_id, user_id, checkin_date, data) VALUES (?1, ?2, ?3, ?4)",
+                params![guild_id, user_id, checkin_date, data],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::{DailyPost, Goal, ServerConfig},
+        storage::TempDir,
+    };
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::collections::{HashMap, HashSet};
+
+    const GUILD: &str = "1000";
+    const MEMBER: &str = "2000";
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, d).unwrap()
+    }
+
+    fn checkin(d: u32) -> CheckinRecord {
+        CheckinRecord {
+            user_id: MEMBER.to_string(),
+            goal: Some("read".to_string()),
+            checkin_date: day(d),
+            message_id: Some(format!("{}", 5000 + d)),
+            thread_id: Some("4000".to_string()),
+            daily_post_id: Some("3000".to_string()),
+            created_at: Utc.with_ymd_and_hms(2025, 11, d, 12, 0, 0).unwrap(),
+            before: None,
+        }
+    }
+
+    /// A guild with one member, a daily post, two check-ins and a missed post day
+    fn sample_data() -> BotData {
+        let created = Utc.with_ymd_and_hms(2025, 11, 1, 12, 0, 0).unwrap();
+        let mut data = BotData::default();
+        data.add_or_update_server(ServerConfig::new(GUILD.to_string(), created));
+        let mut user = UserData::new(MEMBER.to_string(), created);
+        user.goals.push(Goal::new("read".to_string(), "Read 20 pages".to_string(), created));
+        data.add_or_update_user(GUILD.to_string(), user);
+        data.set_daily_post(DailyPost {
+            guild_id: GUILD.to_string(),
+            channel_id: "3500".to_string(),
+            message_id: "3000".to_string(),
+            thread_id: Some("4000".to_string()),
+            posted_at: Utc.with_ymd_and_hms(2025, 11, 2, 9, 0, 0).unwrap(),
+            created_at: Utc.with_ymd_and_hms(2025, 11, 2, 9, 0, 0).unwrap(),
+            catch_up_for: None,
+            deadline: None,
+        });
+        data.add_checkin(GUILD.to_string(), checkin(1));
+        data.add_checkin(GUILD.to_string(), checkin(2));
+        data.add_missed_post_day(GUILD.to_string(), day(3));
+        data
+    }
+
+    fn everything() -> PendingChanges {
+        PendingChanges { everything: true, ..PendingChanges::default() }
+    }
+
+    /// The persisted fields, for comparing data sets
+    fn document(data: &BotData) -> Value {
+        serde_json::to_value(data).unwrap()
+    }
+
+    async fn checkin_dates(storage: &SqliteStorage) -> Vec<NaiveDate> {
+        let data = storage.load().await.unwrap();
+        data.get_checkins(GUILD).iter().map(|record| record.checkin_date).collect()
+    }
+
+    #[tokio::test]
+    async fn saved_data_loads_back_unchanged() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        assert!(storage.load().await.unwrap().servers.is_empty());
+
+        let data = sample_data();
+        storage.save(&data, &everything()).await.unwrap();
+        assert_eq!(document(&storage.load().await.unwrap()), document(&data));
+    }
+
+    #[tokio::test]
+    async fn saves_write_only_the_pending_changes() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let mut data = sample_data();
+        storage.save(&data, &everything()).await.unwrap();
+
+        // A row lost behind the data set's back stays lost while nothing touches it
+        storage.conn.lock().unwrap().execute("DELETE FROM daily_posts", []).unwrap();
+        data.checkins_mut(GUILD).remove(0);
+        data.add_checkin(GUILD.to_string(), checkin(4));
+        data.get_user_mut(GUILD, MEMBER).unwrap().timezone = Some("Europe/Berlin".to_string());
+        let changes = PendingChanges {
+            users: HashSet::from([(GUILD.to_string(), MEMBER.to_string())]),
+            rewritten_checkins: HashSet::from([GUILD.to_string()]),
+            ..PendingChanges::default()
+        };
+        storage.save(&data, &changes).await.unwrap();
+
+        let loaded = storage.load().await.unwrap();
+        assert!(loaded.daily_posts.is_empty());
+        assert_eq!(loaded.get_user(GUILD, MEMBER).unwrap().timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(checkin_dates(&storage).await, vec![day(2), day(4)]);
+
+        // Appended check-ins only insert the new records
+        data.add_checkin(GUILD.to_string(), checkin(5));
+        let changes = PendingChanges {
+            new_checkins: HashMap::from([(GUILD.to_string(), 1)]),
+            ..PendingChanges::default()
+        };
+        storage.save(&data, &changes).await.unwrap();
+        assert_eq!(checkin_dates(&storage).await, vec![day(2), day(4), day(5)]);
+
+        // Records gone from the data set are deleted
+        data.users.clear();
+        let changes = PendingChanges {
+            users: HashSet::from([(GUILD.to_string(), MEMBER.to_string())]),
+            ..PendingChanges::default()
+        };
+        storage.save(&data, &changes).await.unwrap();
+        assert!(storage.load().await.unwrap().get_user(GUILD, MEMBER).is_none());
+
+        // Rewriting everything restores the lost row
+        storage.save(&data, &everything()).await.unwrap();
+        assert_eq!(document(&storage.load().await.unwrap()), document(&data));
+    }
+
+    #[tokio::test]
+    async fn new_databases_keep_incremental_saves_across_restarts() {
+        let dir = TempDir::new();
+        let path = dir.path().join("bot_data.db");
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::open(&path).unwrap());
+        let mut data = BotData::load(storage).await.unwrap();
+        let user = sample_data().get_user(GUILD, MEMBER).unwrap().clone();
+        data.add_or_update_user(GUILD.to_string(), user);
+        data.save().await.unwrap();
+        drop(data);
+
+        // Reopening must not mistake the rows for data from before versioning
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::open(&path).unwrap());
+        let loaded = BotData::load(storage).await.unwrap();
+        let goal = loaded.get_user(GUILD, MEMBER).unwrap().goal("read").unwrap();
+        assert_eq!(goal.description, "Read 20 pages");
+        assert_eq!(loaded.schema_version, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[tokio::test]
+    async fn json_data_is_imported_only_into_an_empty_database() {
+        let dir = TempDir::new();
+        let json = JsonStorage::new(dir.path().join("bot_data.json"));
+        let storage = SqliteStorage::open(dir.path().join("bot_data.db")).unwrap();
+
+        // Nothing to import yet
+        storage.migrate_from_json(&json).await.unwrap();
+        assert!(storage.load().await.unwrap().servers.is_empty());
+
+        let data = sample_data();
+        json.save(&data, &PendingChanges::default()).await.unwrap();
+        storage.migrate_from_json(&json).await.unwrap();
+        assert_eq!(document(&storage.load().await.unwrap()), document(&data));
+
+        // Once the database has data, the JSON file is left alone
+        let mut newer = sample_data();
+        newer.add_or_update_server(ServerConfig::new("1001".to_string(), Utc::now()));
+        json.save(&newer, &PendingChanges::default()).await.unwrap();
+        storage.migrate_from_json(&json).await.unwrap();
+        assert_eq!(document(&storage.load().await.unwrap()), document(&data));
+    }
+}
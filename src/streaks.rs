@@ -1,14 +1,24 @@
-use crate::{bot::SharedBotData, data::{UserData, BotData}};
-use chrono::{Utc, NaiveDate, Duration};
-use serenity::{
-    model::{
-        channel::Message,
-        id::{GuildId, ChannelId},
-    },
-    prelude::Context,
+use crate::{bot::SharedBotData, data::{week_start, Goal, GoalFrequency, BotData, CheckinRecord, FreezeEventKind, StreakPolicy}, platform::{IncomingMessage, OutgoingMessage, Platform}, schedule::PostSchedule};
+use chrono::{DateTime, Duration, Utc, NaiveDate};
+use serenity::model::{
+    channel::ReactionType,
+    id::{GuildId, ChannelId, MessageId},
 };
 use tracing::{info, debug, error};
 
+/// What became of a check-in attempt
+#[derive(Debug, Clone)]
+pub enum CheckinOutcome {
+    Recorded {
+        goals: Vec<Goal>,        // the goals checked in on, with their updated streaks
+        milestones: Vec<String>, // names of those whose streak just reached a milestone
+    },
+    AlreadyCheckedIn,    // every goal it was for already has a check-in this cycle
+    NotRegistered,
+    NoOpenCheckin,         // there's no daily post, or its response window has closed
+    Rejected(Vec<String>), // it didn't meet the server's check-in requirements, for these reasons
+}
+
 pub struct StreakManager {
     data: SharedBotData,
 }
@@ -19,25 +29,259 @@ impl StreakManager {
     }
 
     /// Process a message to check if it's a valid daily check-in response
-    pub async fn process_message(&self, _ctx: &Context, msg: &Message) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn process_message(&self, platform: &dyn Platform, msg: &IncomingMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Skip bot messages
-        if msg.author.bot {
+        if msg.author_is_bot {
             return Ok(());
         }
 
         // Check if this message is in a daily check-in thread within 24 hours
         if let Some(guild_id) = msg.guild_id {
-            let message_time = chrono::DateTime::<Utc>::from_timestamp(msg.timestamp.unix_timestamp(), 0)
-                .unwrap_or_else(|| Utc::now());
+            let message_time = match msg.timestamp {
+                Some(time) => time,
+                None => self.data.read().await.now(),
+            };
             if self.is_valid_checkin_response(guild_id, msg.channel_id, &message_time).await {
-                info!("Processing check-in response from user {} in guild {}", msg.author.id, guild_id);
-                self.record_checkin(guild_id, msg.author.id, &message_time).await?;
+                info!("Processing check-in response from user {} in guild {}", msg.author_id, guild_id);
+                let outcome = self
+                    .record_checkin(guild_id, msg.author_id, Some(msg.id), Some(msg.channel_id), &message_time, &msg.content, msg.attachments)
+                    .await?;
+                debug!("Check-in from user {} in guild {}: {:?}", msg.author_id, guild_id, outcome);
+                match outcome {
+                    CheckinOutcome::Recorded { goals, milestones } => self.acknowledge(platform, msg, guild_id, &goals, &milestones).await,
+                    CheckinOutcome::Rejected(problems) => Self::explain_rejection(platform, msg, &problems).await,
+                    _ => {}
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Re-validate an edited thread reply while the check-in is open: a check-in edited so it
+    /// no longer meets the server's requirements is revoked, and a reply that didn't count
+    /// before is counted once it does
+    pub async fn process_message_update(
+        &self,
+        platform: &dyn Platform,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let now = self.data.read().await.now();
+        if !self.is_valid_checkin_response(guild_id, channel_id, &now).await {
+            return Ok(());
+        }
+        let msg = platform.fetch_message(channel_id, message_id).await?;
+        if msg.author_is_bot {
+            return Ok(());
+        }
+
+        let guild_id_str = guild_id.to_string();
+        let message_id_str = message_id.to_string();
+        let (counted, requirements) = {
+            let data = self.data.read().await;
+            let counted = data
+                .get_checkins(&guild_id_str)
+                .iter()
+                .any(|record| record.message_id.as_deref() == Some(&message_id_str));
+            let requirements = data
+                .get_server_config(&guild_id_str)
+                .map(|config| config.checkin_requirements.clone())
+                .unwrap_or_default();
+            (counted, requirements)
+        };
+
+        if counted {
+            let problems = requirements.problems(&msg.content, msg.attachments);
+            if !problems.is_empty() {
+                let mut data = self.data.write().await;
+                let revoked = Self::revoke_checkins(&mut data, &guild_id_str, &message_id_str);
+                info!("Revoked {} check-in(s) by user {} in guild {} after an edit", revoked.len(), msg.author_id, guild_id);
+                data.save().await?;
+                drop(data);
+                Self::explain_rejection(platform, &msg, &problems).await;
+            }
+            return Ok(());
+        }
+
+        let message_time = msg.timestamp.unwrap_or(now);
+        let outcome = self
+            .record_checkin(guild_id, msg.author_id, Some(msg.id), Some(msg.channel_id), &message_time, &msg.content, msg.attachments)
+            .await?;
+        debug!("Edited check-in from user {} in guild {}: {:?}", msg.author_id, guild_id, outcome);
+        let flagged = msg.has_own_reaction("❌");
+        match outcome {
+            CheckinOutcome::Recorded { goals, milestones } => {
+                if flagged {
+                    if let Err(e) = platform.remove_reaction(msg.channel_id, msg.id, '❌'.into()).await {
+                        error!("Failed to clear the rejection of edited check-in {}: {}", msg.id, e);
+                    }
+                }
+                self.acknowledge(platform, &msg, guild_id, &goals, &milestones).await;
+            }
+            // Explain once, not on every edit
+            CheckinOutcome::Rejected(problems) if !flagged => Self::explain_rejection(platform, &msg, &problems).await,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Revoke the check-ins made by a deleted message, unless the server keeps them
+    pub async fn process_message_delete(
+        &self,
+        guild_id: GuildId,
+        message_id: MessageId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut data = self.data.write().await;
+        let guild_id_str = guild_id.to_string();
+        let keep = data
+            .get_server_config(&guild_id_str)
+            .map(|config| config.keep_deleted_checkins)
+            .unwrap_or(false);
+        if keep {
+            return Ok(());
+        }
+
+        let revoked = Self::revoke_checkins(&mut data, &guild_id_str, &message_id.to_string());
+        if let Some(record) = revoked.first() {
+            info!("Revoked {} check-in(s) by user {} in guild {} after their message was deleted", revoked.len(), record.user_id, guild_id);
+            data.save().await?;
+        }
+        Ok(())
+    }
+
+    /// Take back the check-ins a message made and recompute the streaks of their goals: each
+    /// goal returns to its state from before the check-in, and its later check-ins are replayed
+    /// on top. Records from before check-ins kept that state are removed without touching the
+    /// streak. Returns the revoked records.
+    pub fn revoke_checkins(data: &mut BotData, guild_id: &str, message_id: &str) -> Vec<CheckinRecord> {
+        let made_by_message = |record: &CheckinRecord| record.message_id.as_deref() == Some(message_id);
+        if !data.get_checkins(guild_id).iter().any(made_by_message) {
+            return Vec::new();
+        }
+        let is_checkin_day = Self::checkin_day_filter(data, guild_id);
+        let policy = Self::streak_policy(data, guild_id);
+        let now = data.now();
+
+        let (revoked, mut log): (Vec<_>, Vec<_>) = std::mem::take(data.checkins_mut(guild_id))
+            .into_iter()
+            .partition(made_by_message);
+        for record in &revoked {
+            let (Some(goal_name), Some(before)) = (&record.goal, &record.before) else {
+                continue;
+            };
+            let Some(user) = data.get_user_mut(guild_id, &record.user_id) else {
+                continue;
+            };
+            let is_away = user.away_filter();
+            let is_expected_day = |date: NaiveDate| is_checkin_day(date) && !is_away(date);
+            let Some(goal) = user.goal_mut(goal_name) else {
+                continue;
+            };
+
+            goal.restore(before);
+            goal.updated_at = now;
+            let later = log.iter_mut().filter(|later| {
+                later.user_id == record.user_id
+                    && later.goal.as_ref() == Some(goal_name)
+                    && later.checkin_date > record.checkin_date
+            });
+            for later in later {
+                later.before = Some(goal.snapshot());
+                Self::apply_checkin(goal, later.checkin_date, &policy, is_expected_day);
+            }
+        }
+        *data.checkins_mut(guild_id) = log;
+
+        revoked
+    }
+
+    /// Let the author of a thread reply know it counted, as the server's check-in feedback says
+    async fn acknowledge(&self, platform: &dyn Platform, msg: &IncomingMessage, guild_id: GuildId, goals: &[Goal], milestones: &[String]) {
+        let feedback = {
+            let data = self.data.read().await;
+            data.get_server_config(&guild_id.to_string())
+                .map(|config| config.checkin_feedback.clone())
+                .unwrap_or_default()
+        };
+
+        let emoji = if milestones.is_empty() {
+            feedback.emoji
+        } else {
+            feedback.milestone_emoji.or(feedback.emoji)
+        };
+        if let Some(reaction) = emoji.and_then(|emoji| ReactionType::try_from(emoji.as_str()).ok()) {
+            if let Err(e) = platform.react(msg.channel_id, msg.id, reaction).await {
+                error!("Failed to react to check-in {}: {}", msg.id, e);
+            }
+        }
+
+        if feedback.reply_with_streak {
+            let reply = OutgoingMessage::reply(msg.id, Self::describe_streaks(goals, milestones));
+            if let Err(e) = platform.send_message(msg.channel_id, reply).await {
+                error!("Failed to reply to check-in {}: {}", msg.id, e);
+            }
+        }
+    }
+
+    /// One line per goal checked in on with its new streak, celebrating milestones
+    pub fn describe_streaks(goals: &[Goal], milestones: &[String]) -> String {
+        goals
+            .iter()
+            .map(|goal| {
+                let celebration = if milestones.contains(&goal.name) { " 🎉 Milestone!" } else { "" };
+                format!("`{}` 🔥 {} {}{}", goal.name, goal.current_streak, goal.frequency.streak_unit(), celebration)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Streaks worth celebrating: 7, 30, 50 and 365, and every multiple of 100
+    pub fn is_streak_milestone(streak: u32) -> bool {
+        matches!(streak, 7 | 30 | 50 | 365) || (streak > 0 && streak.is_multiple_of(100))
+    }
+
+    /// React to a thread reply that didn't meet the server's requirements, and reply with why
+    async fn explain_rejection(platform: &dyn Platform, msg: &IncomingMessage, problems: &[String]) {
+        if let Err(e) = platform.react(msg.channel_id, msg.id, '❌'.into()).await {
+            error!("Failed to react to rejected check-in {}: {}", msg.id, e);
+        }
+        let reply = OutgoingMessage::reply(
+            msg.id,
+            format!("This didn't count as a check-in: {}. Post again once it does.", problems.join("; ")),
+        );
+        if let Err(e) = platform.send_message(msg.channel_id, reply).await {
+            error!("Failed to explain rejected check-in {}: {}", msg.id, e);
+        }
+    }
+
+    /// Check in now without replying in the thread, from the daily post's button or `/checkin`.
+    /// `content` is read for `#name` goal tags like a thread reply.
+    pub async fn checkin_directly(
+        &self,
+        guild_id: GuildId,
+        user_id: serenity::model::id::UserId,
+        content: &str,
+        attachments: usize,
+    ) -> Result<CheckinOutcome, Box<dyn std::error::Error + Send + Sync>> {
+        let (now, window_open) = {
+            let data = self.data.read().await;
+            let now = data.now();
+            let window_open = data.daily_posts
+                .get(&guild_id.to_string())
+                .map(|daily_post| now <= daily_post.response_deadline())
+                .unwrap_or(false);
+            (now, window_open)
+        };
+        if !window_open {
+            return Ok(CheckinOutcome::NoOpenCheckin);
+        }
+
+        self.record_checkin(guild_id, user_id, None, None, &now, content, attachments).await
+    }
+
     /// Check if a message is a valid check-in response (in thread + within 24 hours of post)
     async fn is_valid_checkin_response(&self, guild_id: GuildId, channel_id: ChannelId, message_time: &chrono::DateTime<Utc>) -> bool {
         let data = self.data.read().await;
@@ -48,8 +292,8 @@ impl StreakManager {
             // Check if this is the correct thread
             if let Some(thread_id) = &daily_post.thread_id {
                 if thread_id == &channel_id_str {
-                    // Calculate 24-hour deadline: daily post time + 24 hours
-                    let deadline = daily_post.posted_at + Duration::hours(24);
+                    // 24 hours after the daily post, or the adjusted deadline of a late post
+                    let deadline = daily_post.response_deadline();
                     
                     // Check if message was posted before the deadline
                     return *message_time <= deadline;
@@ -60,50 +304,104 @@ impl StreakManager {
         false
     }
 
-    /// Record a check-in and update user streak
+    /// Record a check-in and update the streaks of the goals it is for
+    #[allow(clippy::too_many_arguments)]
     async fn record_checkin(
         &self,
         guild_id: GuildId,
         user_id: serenity::model::id::UserId,
+        message_id: Option<MessageId>,
+        thread_id: Option<ChannelId>,
         message_time: &chrono::DateTime<Utc>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        content: &str,
+        attachments: usize,
+    ) -> Result<CheckinOutcome, Box<dyn std::error::Error + Send + Sync>> {
         let mut data = self.data.write().await;
+        let now = data.now();
         let guild_id_str = guild_id.to_string();
         let user_id_str = user_id.to_string();
-        let response_date = message_time.date_naive();
+        // The day in the member's own timezone; late posts made up after downtime credit
+        // the day they stand in for
+        let timezone = data.user_timezone(&guild_id_str, &user_id_str);
+        let response_date = data.daily_posts
+            .get(&guild_id_str)
+            .and_then(|post| post.catch_up_for)
+            .unwrap_or(message_time.with_timezone(&timezone).date_naive());
 
-        // Check if user already has a response for this daily post cycle (before borrowing mutably)
-        let post_date = data.daily_posts.get(&guild_id_str).map(|post| post.posted_at.date_naive());
+        let daily_post_id = data.daily_posts.get(&guild_id_str).map(|post| post.message_id.clone());
+        let is_checkin_day = Self::checkin_day_filter(&data, &guild_id_str);
+        let policy = Self::streak_policy(&data, &guild_id_str);
+        let requirements = data
+            .get_server_config(&guild_id_str)
+            .map(|config| config.checkin_requirements.clone())
+            .unwrap_or_default();
         
         // Get the user
-        let user = match data.users
-            .get_mut(&guild_id_str)
-            .and_then(|guild_users| guild_users.get_mut(&user_id_str))
-        {
+        let user = match data.get_user_mut(&guild_id_str, &user_id_str) {
             Some(user) if user.is_active => user,
             Some(_) => {
                 debug!("User {} is inactive in guild {}, ignoring check-in", user_id, guild_id);
-                return Ok(());
+                return Ok(CheckinOutcome::NotRegistered);
             }
             None => {
                 debug!("User {} not registered in guild {}, ignoring check-in", user_id, guild_id);
-                return Ok(());
+                return Ok(CheckinOutcome::NotRegistered);
             }
         };
-        
-        if let Some(post_date) = post_date {
-            if let Some(last_checkin) = user.last_checkin_date {
-                // If they already checked in on or after the day this post was created, skip
-                if last_checkin >= post_date {
-                    debug!("User {} already checked in for this daily post cycle in guild {}", user_id, guild_id);
-                    return Ok(());
+
+        // `#name` tags pick goals; an untagged check-in counts for all of them
+        let targets = Self::tagged_goals(content, &user.goals);
+        let is_away = user.away_filter();
+        let is_expected_day = |date: NaiveDate| is_checkin_day(date) && !is_away(date);
+
+        let due: Vec<String> = user
+            .active_goals()
+            .filter(|goal| targets.is_empty() || targets.contains(&goal.name))
+            .filter(|goal| match goal.last_checkin_date {
+                // One check-in per day: skip goals already checked in on for this day (or a later one)
+                Some(last_checkin) if last_checkin >= response_date => {
+                    debug!("User {} already checked in on {} for {} in guild {}", user_id, goal.name, response_date, guild_id);
+                    false
                 }
-            }
+                _ => true,
+            })
+            .map(|goal| goal.name.clone())
+            .collect();
+        if due.is_empty() {
+            return Ok(CheckinOutcome::AlreadyCheckedIn);
+        }
+
+        // Only check-ins that would count are held to the server's requirements,
+        // so chatting in the thread afterwards is never flagged
+        let problems = requirements.problems(content, attachments);
+        if !problems.is_empty() {
+            debug!("Rejected check-in from user {} in guild {}: {:?}", user_id, guild_id, problems);
+            return Ok(CheckinOutcome::Rejected(problems));
+        }
+
+        let mut checked_in = Vec::new();
+        for goal in user.goals.iter_mut().filter(|goal| due.contains(&goal.name)) {
+            // Update the goal's streak, remembering the old one in case the check-in is revoked
+            let before = goal.snapshot();
+            Self::apply_checkin(goal, response_date, &policy, is_expected_day);
+            goal.updated_at = now;
+            info!("User {} checked in on {}! New streak: {} days", user_id, goal.name, goal.current_streak);
+            checked_in.push((goal.clone(), before));
         }
 
-        // Update user streak
-        Self::update_user_streak(user, response_date);
-        info!("User {} checked in! New streak: {} days", user_id, user.current_streak);
+        // Keep the check-ins in the guild's history log
+        for (goal, before) in &checked_in {
+            data.add_checkin(guild_id_str.clone(), CheckinRecord {
+                user_id: user_id_str.clone(),
+                goal: Some(goal.name.clone()),
+                checkin_date: response_date,
+                message_id: message_id.map(|id| id.to_string()),
+                thread_id: thread_id.map(|id| id.to_string()),
+                daily_post_id: daily_post_id.clone(),
+                created_at: now,
+                before: Some(before.clone()),
+            });
+        }
 
         // Save data
         if let Err(e) = data.save().await {
@@ -111,79 +409,232 @@ impl StreakManager {
             return Err(e.into());
         }
 
-        Ok(())
+        let milestones = checked_in
+            .iter()
+            .filter(|(goal, before)| (before.current_streak + 1..=goal.current_streak).any(Self::is_streak_milestone))
+            .map(|(goal, _)| goal.name.clone())
+            .collect();
+        let goals = checked_in.into_iter().map(|(goal, _)| goal).collect();
+        Ok(CheckinOutcome::Recorded { goals, milestones })
     }
 
-    /// Update a user's streak based on their check-in
-    pub fn update_user_streak(user: &mut UserData, response_date: NaiveDate) {
-        match user.last_checkin_date {
+    /// Update a goal's streak for a check-in on `response_date`, daily or weekly as the goal counts
+    fn apply_checkin(goal: &mut Goal, response_date: NaiveDate, policy: &StreakPolicy, is_expected_day: impl Fn(NaiveDate) -> bool) {
+        match goal.frequency {
+            GoalFrequency::Daily => Self::update_goal_streak(goal, response_date, policy, is_expected_day),
+            GoalFrequency::Weekly { times } => Self::update_weekly_streak(goal, response_date, times, is_expected_day),
+        }
+    }
+
+    /// Names of the user's active goals tagged as `#name` in a message
+    pub fn tagged_goals(content: &str, goals: &[Goal]) -> Vec<String> {
+        let content = content.to_lowercase();
+        let tags: Vec<&str> = content
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('#'))
+            .map(|tag| tag.trim_end_matches(|c: char| !c.is_alphanumeric()))
+            .collect();
+        goals
+            .iter()
+            .filter(|goal| goal.is_active && tags.contains(&goal.name.as_str()))
+            .map(|goal| goal.name.clone())
+            .collect()
+    }
+
+    /// Update a goal's streak based on a check-in.
+    /// `is_expected_day` tells which dates the user was expected to check in on; days it
+    /// excludes (e.g. weekends on a weekday-only schedule, or a break) never break a streak.
+    pub fn update_goal_streak(
+        goal: &mut Goal,
+        response_date: NaiveDate,
+        policy: &StreakPolicy,
+        is_expected_day: impl Fn(NaiveDate) -> bool,
+    ) {
+        let previous_streak = goal.current_streak;
+        match goal.last_checkin_date {
             None => {
                 // First check-in ever
-                user.current_streak = 1;
-                user.last_checkin_date = Some(response_date);
+                goal.current_streak = 1;
+                goal.last_checkin_date = Some(response_date);
             }
             Some(last_date) => {
                 if last_date == response_date {
                     // Already checked in today (shouldn't happen with our duplicate check)
                     return;
-                } else if last_date == response_date.pred_opt().unwrap_or(response_date) {
-                    // Checked in yesterday - continue streak
-                    user.current_streak += 1;
-                    user.last_checkin_date = Some(response_date);
-                } else if last_date < response_date.pred_opt().unwrap_or(response_date) {
-                    // Missed at least one day - check for grace period
-                    if Self::should_apply_grace_period(user, last_date, response_date) {
-                        // Grace period applies - continue streak but mark grace period start
-                        user.current_streak += 1;
-                        user.last_checkin_date = Some(response_date);
-                        if user.grace_period_start.is_none() {
-                            user.grace_period_start = Some(last_date.succ_opt().unwrap_or(response_date));
+                } else if last_date < response_date {
+                    let missed = Self::missed_days(goal, last_date, response_date, &is_expected_day);
+                    if missed.is_empty() {
+                        // Checked in on the previous check-in day - continue streak
+                        goal.current_streak += 1;
+                        goal.last_checkin_date = Some(response_date);
+                    } else if let Some(freezes) = Self::freezes_needed(goal, policy, &missed, response_date) {
+                        // Missed at least one day, but grace and freeze tokens cover it - continue streak
+                        let (graced, frozen) = missed.split_at(missed.len() - freezes);
+                        Self::spend_freezes(goal, frozen);
+                        goal.current_streak += 1;
+                        if !graced.is_empty() {
+                            if policy.grace_days_count_toward_streak {
+                                goal.current_streak += graced.len() as u32;
+                            }
+                            if goal.grace_period_start.is_none() {
+                                goal.grace_period_start = graced.first().copied();
+                            }
+                            let window_start = Self::grace_window_start(policy, response_date);
+                            goal.grace_days.retain(|day| *day > window_start);
+                            goal.grace_days.extend_from_slice(graced);
                         }
+                        goal.last_checkin_date = Some(response_date);
                     } else {
                         // No grace period or grace period exceeded - reset streak
-                        user.current_streak = 1;
-                        user.last_checkin_date = Some(response_date);
-                        user.grace_period_start = None;
+                        goal.current_streak = 1;
+                        goal.last_checkin_date = Some(response_date);
+                        goal.grace_period_start = None;
+                        goal.grace_days.clear();
                     }
                 } else {
                     // Future date (shouldn't happen)
-                    debug!("Warning: Check-in date in the future for user {}", user.user_id);
+                    debug!("Warning: Check-in date in the future for goal {}", goal.name);
                 }
             }
         }
 
+        Self::award_freezes(goal, policy, previous_streak, response_date);
+
         // Update longest streak if current is higher
-        if user.current_streak > user.longest_streak {
-            user.longest_streak = user.current_streak;
+        if goal.current_streak > goal.longest_streak {
+            goal.longest_streak = goal.current_streak;
         }
+    }
+
+    /// Update a weekly goal's streak based on a check-in. The streak grows once per week,
+    /// on the check-in that meets the week's target.
+    pub fn update_weekly_streak(
+        goal: &mut Goal,
+        response_date: NaiveDate,
+        times: u32,
+        is_expected_day: impl Fn(NaiveDate) -> bool,
+    ) {
+        if goal.last_checkin_date >= Some(response_date) {
+            // Already checked in today, or a date in the past (shouldn't happen)
+            return;
+        }
+        let week = week_start(response_date);
+        goal.week_checkins = goal.checkins_in_week_of(response_date) + 1;
+        goal.last_checkin_date = Some(response_date);
+
+        let target = Self::weekly_target(times, week, &is_expected_day);
+        if goal.last_met_week != Some(week) && target > 0 && goal.week_checkins >= target {
+            Self::credit_week(goal, times, week, &is_expected_day);
+        }
+    }
 
-        // Update timestamp
-        user.updated_at = Utc::now();
+    /// Check-ins a weekly goal needs in the week starting `week`: its target, or fewer if the
+    /// week has fewer expected days (breaks, schedules skipping days, missed posts)
+    fn weekly_target(times: u32, week: NaiveDate, is_expected_day: impl Fn(NaiveDate) -> bool) -> u32 {
+        let expected_days = week.iter_days().take(7).filter(|date| is_expected_day(*date)).count() as u32;
+        times.min(expected_days)
     }
 
-    /// Free function for guild-specific streak maintenance
-    /// Can be called inline without needing StreakManager instance
-    pub async fn reset_streaks_for_guild(data: &mut BotData, guild_id: &str) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
-        let yesterday = Utc::now().date_naive().pred_opt().unwrap_or(Utc::now().date_naive());
+    /// Whether a week after the goal's last met week and before `week` had a target it didn't meet
+    fn missed_a_week(goal: &Goal, times: u32, week: NaiveDate, is_expected_day: impl Fn(NaiveDate) -> bool) -> bool {
+        let Some(last_met) = goal.last_met_week else {
+            return true;
+        };
+        (1..)
+            .map(|n| last_met + Duration::weeks(n))
+            .take_while(|earlier| *earlier < week)
+            .any(|earlier| Self::weekly_target(times, earlier, &is_expected_day) > 0)
+    }
+
+    /// Count the week starting `week` as met, restarting the streak if an earlier week wasn't
+    fn credit_week(goal: &mut Goal, times: u32, week: NaiveDate, is_expected_day: impl Fn(NaiveDate) -> bool) {
+        if Self::missed_a_week(goal, times, week, is_expected_day) {
+            goal.current_streak = 0;
+        }
+        goal.current_streak += 1;
+        goal.last_met_week = Some(week);
+        if goal.current_streak > goal.longest_streak {
+            goal.longest_streak = goal.current_streak;
+        }
+    }
+
+    /// Weekly goal maintenance: settle the week before `today`'s once it's over, and reset
+    /// the streak if it fell short. Returns whether the streak was reset.
+    fn close_weekly_goal(goal: &mut Goal, times: u32, today: NaiveDate, now: DateTime<Utc>, is_expected_day: impl Fn(NaiveDate) -> bool) -> bool {
+        let this_week = week_start(today);
+        let last_week = this_week - Duration::weeks(1);
+
+        // A target that shrank after the week's check-ins (e.g. the bot missed a post) is met in hindsight
+        if goal.last_met_week != Some(last_week) && goal.last_checkin_date.map(week_start) == Some(last_week) {
+            let target = Self::weekly_target(times, last_week, &is_expected_day);
+            if target > 0 && goal.week_checkins >= target {
+                Self::credit_week(goal, times, last_week, &is_expected_day);
+                goal.updated_at = now;
+            }
+        }
+
+        if goal.current_streak > 0 && Self::missed_a_week(goal, times, this_week, &is_expected_day) {
+            goal.current_streak = 0;
+            goal.updated_at = now;
+            return true;
+        }
+        false
+    }
+
+    /// Free function for guild-specific streak maintenance, run before the post scheduled at
+    /// `cycle_start`. Each member's days up to the one `cycle_start` falls on in their
+    /// timezone are judged. Can be called inline without needing StreakManager instance
+    pub async fn reset_streaks_for_guild(data: &mut BotData, guild_id: &str, cycle_start: DateTime<Utc>) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+        let is_checkin_day = Self::checkin_day_filter(data, guild_id);
+        let policy = Self::streak_policy(data, guild_id);
+        let guild_timezone = data.guild_timezone(guild_id);
+        let now = data.now();
         let mut reset_count = 0;
 
-        if let Some(guild_users) = data.users.get_mut(guild_id) {
+        if let Some(guild_users) = data.get_guild_users_mut(guild_id) {
             for (user_id, user) in guild_users.iter_mut() {
                 if !user.is_active {
                     continue;
                 }
+                let today = cycle_start.with_timezone(&user.timezone_or(guild_timezone)).date_naive();
+                let is_away = user.away_filter();
+                let is_expected_day = |date: NaiveDate| is_checkin_day(date) && !is_away(date);
+
+                for goal in user.goals.iter_mut().filter(|goal| goal.is_active) {
+                    // Weekly goals are judged once a week is over
+                    if let GoalFrequency::Weekly { times } = goal.frequency {
+                        if Self::close_weekly_goal(goal, times, today, now, is_expected_day) {
+                            reset_count += 1;
+                            info!("Reset {} weekly streak for user {} in guild {} due to a missed target", goal.name, user_id, guild_id);
+                        }
+                        continue;
+                    }
 
-                // Check if user missed yesterday's check-in
-                if let Some(last_checkin) = user.last_checkin_date {
-                    if last_checkin < yesterday {
-                        // User missed check-in, check if grace period applies
-                        if !Self::should_apply_grace_period(user, last_checkin, yesterday.succ_opt().unwrap_or(yesterday)) {
+                    // Check if the goal missed a check-in day since its last check-in
+                    let Some(last_checkin) = goal.last_checkin_date else {
+                        continue;
+                    };
+                    let missed = Self::missed_days(goal, last_checkin, today, is_expected_day);
+                    if missed.is_empty() {
+                        continue;
+                    }
+
+                    // Missed check-in: grace period first, then freeze tokens
+                    match Self::freezes_needed(goal, &policy, &missed, today) {
+                        Some(0) => {}
+                        Some(freezes) => {
+                            Self::spend_freezes(goal, &missed[missed.len() - freezes..]);
+                            goal.updated_at = now;
+                            info!("Used {} freeze token(s) on {} for user {} in guild {}", freezes, goal.name, user_id, guild_id);
+                        }
+                        None => {
                             // Reset streak
-                            user.current_streak = 0;
-                            user.grace_period_start = None;
-                            user.updated_at = Utc::now();
+                            goal.current_streak = 0;
+                            goal.grace_period_start = None;
+                            goal.grace_days.clear();
+                            goal.updated_at = now;
                             reset_count += 1;
-                            info!("Reset streak for user {} in guild {} due to missed check-in", user_id, guild_id);
+                            info!("Reset {} streak for user {} in guild {} due to missed check-in", goal.name, user_id, guild_id);
                         }
                     }
                 }
@@ -193,28 +644,786 @@ impl StreakManager {
         Ok(reset_count)
     }
 
-    /// Helper function for grace period logic
-    fn should_apply_grace_period(user: &UserData, last_checkin: NaiveDate, today: NaiveDate) -> bool {
-        // Grace period only applies to streaks of 30 days or more
-        if user.current_streak < 30 {
+    /// Which dates a guild's members are expected to check in on: days its post schedule
+    /// fires, minus days the bot failed to post. Without a valid schedule every day counts.
+    pub fn checkin_day_filter(data: &BotData, guild_id: &str) -> impl Fn(NaiveDate) -> bool {
+        let schedule = data.get_server_config(guild_id).and_then(|config| {
+            let schedule = PostSchedule::for_server(config).ok()?;
+            let tz: chrono_tz::Tz = config.timezone.parse().ok()?;
+            Some((schedule, tz))
+        });
+        let missed_post_days = data.missed_post_days.get(guild_id).cloned().unwrap_or_default();
+
+        move |date| {
+            if missed_post_days.binary_search(&date).is_ok() {
+                return false;
+            }
+            match &schedule {
+                Some((schedule, tz)) => schedule.fires_on(date, *tz),
+                None => true,
+            }
+        }
+    }
+
+    /// The guild's streak policy, or the defaults if the guild has no configuration yet
+    fn streak_policy(data: &BotData, guild_id: &str) -> StreakPolicy {
+        data.get_server_config(guild_id)
+            .map(|config| config.streak_policy.clone())
+            .unwrap_or_default()
+    }
+
+    /// Expected check-in days strictly between the last check-in and `today` that no freeze token covers
+    fn missed_days(goal: &Goal, last_checkin: NaiveDate, today: NaiveDate, is_expected_day: impl Fn(NaiveDate) -> bool) -> Vec<NaiveDate> {
+        last_checkin
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date < today)
+            .filter(|date| is_expected_day(*date) && !goal.is_frozen(*date))
+            .collect()
+    }
+
+    /// How many freeze tokens it takes to keep the streak alive despite the `missed` days,
+    /// or `None` if the goal doesn't hold enough. The grace period is used first; tokens
+    /// cover the most recent days it can't.
+    fn freezes_needed(goal: &Goal, policy: &StreakPolicy, missed: &[NaiveDate], today: NaiveDate) -> Option<usize> {
+        if goal.current_streak == 0 {
+            // Nothing left to protect
+            return None;
+        }
+        let available = (goal.freeze_tokens as usize).min(missed.len());
+        (0..=available).find(|freezes| {
+            let graced = &missed[..missed.len() - freezes];
+            graced.is_empty() || Self::should_apply_grace_period(goal, policy, graced, today)
+        })
+    }
+
+    fn spend_freezes(goal: &mut Goal, days: &[NaiveDate]) {
+        for day in days {
+            goal.freeze_tokens -= 1;
+            goal.record_freeze_event(FreezeEventKind::Used, *day);
+        }
+    }
+
+    /// Award a freeze token for every streak milestone passed since `previous_streak`, up to the cap
+    fn award_freezes(goal: &mut Goal, policy: &StreakPolicy, previous_streak: u32, today: NaiveDate) {
+        if policy.freeze_every_days == 0 || goal.current_streak <= previous_streak {
+            return;
+        }
+        let milestones = goal.current_streak / policy.freeze_every_days - previous_streak / policy.freeze_every_days;
+        for _ in 0..milestones {
+            if goal.freeze_tokens >= policy.freeze_max_tokens {
+                break;
+            }
+            goal.freeze_tokens += 1;
+            goal.record_freeze_event(FreezeEventKind::Earned, today);
+        }
+    }
+
+    /// Grace days on or before this date have left the policy's rolling window
+    fn grace_window_start(policy: &StreakPolicy, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(policy.grace_window_days as i64)
+    }
+
+    /// Whether the guild's policy excuses the `missed` check-in days before `today`
+    fn should_apply_grace_period(goal: &Goal, policy: &StreakPolicy, missed: &[NaiveDate], today: NaiveDate) -> bool {
+        // Grace period only applies to streaks that are long enough
+        if policy.grace_max_consecutive_misses == 0 || goal.current_streak < policy.grace_min_streak {
             return false;
         }
 
-        // Calculate days missed
-        let days_missed = today.signed_duration_since(last_checkin).num_days() - 1;
+        // Too many check-in days missed in a row
+        if missed.len() > policy.grace_max_consecutive_misses as usize {
+            return false;
+        }
+
+        // Grace days already spent within the rolling window, plus the ones this would spend
+        let window_start = Self::grace_window_start(policy, today);
+        let used = goal
+            .grace_days
+            .iter()
+            .filter(|day| **day > window_start && !missed.contains(day))
+            .count();
+        used + missed.len() <= policy.grace_max_days_per_window as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::FakeClock, data::{CheckinRequirements, DailyPost, PausePeriod, RestDays, ServerConfig, UserData}};
+    use serenity::model::id::UserId;
+    use chrono::{Datelike, Weekday};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, day).unwrap()
+    }
+
+    fn goal_with_streak(streak: u32, last_checkin: NaiveDate) -> Goal {
+        let mut goal = Goal::new("read".to_string(), "Read".to_string(), post_at(1));
+        goal.current_streak = streak;
+        goal.longest_streak = streak;
+        goal.last_checkin_date = Some(last_checkin);
+        goal
+    }
+
+    /// The 09:00 UTC post on a day of `date`'s month
+    fn post_at(day: u32) -> DateTime<Utc> {
+        date(day).and_hms_opt(9, 0, 0).unwrap().and_utc()
+    }
+
+    fn every_day(_: NaiveDate) -> bool {
+        true
+    }
+
+    #[test]
+    fn milestones_are_the_listed_streaks_and_every_hundred() {
+        for streak in [7, 30, 50, 100, 200, 365, 1000] {
+            assert!(StreakManager::is_streak_milestone(streak), "{} should be a milestone", streak);
+        }
+        for streak in [0, 1, 14, 60, 150, 250, 366] {
+            assert!(!StreakManager::is_streak_milestone(streak), "{} should not be a milestone", streak);
+        }
+    }
+
+    #[test]
+    fn short_streaks_get_no_grace() {
+        let mut goal = goal_with_streak(29, date(10));
+        StreakManager::update_goal_streak(&mut goal, date(12), &StreakPolicy::default(), every_day);
+        assert_eq!(goal.current_streak, 1);
+    }
 
-        // Grace period allows up to 2 missed days
-        if days_missed <= 2 {
-            // Check if we're still within the overall grace period window
-            if let Some(grace_start) = user.grace_period_start {
-                let grace_days_used = today.signed_duration_since(grace_start).num_days();
-                grace_days_used <= 2
-            } else {
-                // First time using grace period
-                true
+    #[test]
+    fn grace_covers_up_to_the_consecutive_miss_limit() {
+        let policy = StreakPolicy::default();
+
+        let mut goal = goal_with_streak(30, date(10));
+        StreakManager::update_goal_streak(&mut goal, date(13), &policy, every_day);
+        assert_eq!(goal.current_streak, 31);
+        assert_eq!(goal.grace_days, vec![date(11), date(12)]);
+
+        let mut goal = goal_with_streak(30, date(10));
+        StreakManager::update_goal_streak(&mut goal, date(14), &policy, every_day);
+        assert_eq!(goal.current_streak, 1);
+    }
+
+    #[test]
+    fn grace_days_are_limited_per_rolling_window() {
+        let policy = StreakPolicy {
+            grace_max_days_per_window: 2,
+            grace_window_days: 7,
+            ..StreakPolicy::default()
+        };
+
+        let mut goal = goal_with_streak(30, date(1));
+        StreakManager::update_goal_streak(&mut goal, date(3), &policy, every_day);
+        StreakManager::update_goal_streak(&mut goal, date(5), &policy, every_day);
+        assert_eq!(goal.current_streak, 32);
+
+        // A third grace day within seven days of the first two breaks the streak
+        let mut exhausted = goal.clone();
+        StreakManager::update_goal_streak(&mut exhausted, date(7), &policy, every_day);
+        assert_eq!(exhausted.current_streak, 1);
+
+        // Once the first grace day has left the window, another one is available
+        for day in 6..=8 {
+            StreakManager::update_goal_streak(&mut goal, date(day), &policy, every_day);
+        }
+        StreakManager::update_goal_streak(&mut goal, date(10), &policy, every_day);
+        assert_eq!(goal.current_streak, 36);
+        assert_eq!(goal.grace_days, vec![date(4), date(9)]);
+    }
+
+    #[test]
+    fn grace_days_can_count_toward_the_streak() {
+        let policy = StreakPolicy {
+            grace_min_streak: 5,
+            grace_days_count_toward_streak: true,
+            ..StreakPolicy::default()
+        };
+
+        let mut goal = goal_with_streak(5, date(10));
+        StreakManager::update_goal_streak(&mut goal, date(13), &policy, every_day);
+        assert_eq!(goal.current_streak, 8);
+        assert_eq!(goal.longest_streak, 8);
+    }
+
+    #[test]
+    fn zero_consecutive_misses_disables_grace() {
+        let policy = StreakPolicy {
+            grace_max_consecutive_misses: 0,
+            ..StreakPolicy::default()
+        };
+
+        let mut goal = goal_with_streak(100, date(10));
+        StreakManager::update_goal_streak(&mut goal, date(12), &policy, every_day);
+        assert_eq!(goal.current_streak, 1);
+    }
+
+    fn guild_with_user(goals: Vec<Goal>, pauses: Vec<PausePeriod>) -> BotData {
+        let mut user = UserData::new("2000".to_string(), post_at(1));
+        user.goals = goals;
+        user.pauses = pauses;
+        let mut data = BotData::default();
+        data.add_or_update_user("1000".to_string(), user);
+        data
+    }
+
+    fn goal_in_guild<'a>(data: &'a BotData, name: &str) -> &'a Goal {
+        data.get_user("1000", "2000").unwrap().goal(name).unwrap()
+    }
+
+    /// A manager for `data` whose clock stands still at `now` until moved
+    fn manager_at(mut data: BotData, now: DateTime<Utc>) -> (StreakManager, Arc<FakeClock>) {
+        let clock = FakeClock::new(now);
+        data.set_clock(clock.clone());
+        (StreakManager::new(Arc::new(RwLock::new(data))), clock)
+    }
+
+    /// The guild's daily post, made at `posted_at`
+    fn daily_post(posted_at: DateTime<Utc>) -> DailyPost {
+        DailyPost {
+            guild_id: "1000".to_string(),
+            channel_id: "3000".to_string(),
+            message_id: posted_at.timestamp().to_string(),
+            thread_id: None,
+            posted_at,
+            created_at: posted_at,
+            catch_up_for: None,
+            deadline: None,
+        }
+    }
+
+    #[test]
+    fn freeze_tokens_are_earned_at_milestones_up_to_the_cap() {
+        let policy = StreakPolicy::default();
+        let mut goal = goal_with_streak(0, date(1));
+        goal.last_checkin_date = None;
+
+        for day in 1..=21 {
+            StreakManager::update_goal_streak(&mut goal, date(day), &policy, every_day);
+        }
+
+        assert_eq!(goal.current_streak, 21);
+        assert_eq!(goal.freeze_tokens, 2);
+        let earned: Vec<_> = goal.freeze_history.iter().map(|event| (event.date, event.streak)).collect();
+        assert_eq!(earned, vec![(date(7), 7), (date(14), 14)]);
+    }
+
+    #[test]
+    fn grace_is_used_before_freeze_tokens() {
+        let mut goal = goal_with_streak(30, date(10));
+        goal.freeze_tokens = 2;
+
+        StreakManager::update_goal_streak(&mut goal, date(13), &StreakPolicy::default(), every_day);
+
+        assert_eq!(goal.current_streak, 31);
+        assert_eq!(goal.freeze_tokens, 2);
+        assert_eq!(goal.grace_days, vec![date(11), date(12)]);
+    }
+
+    #[test]
+    fn freeze_tokens_cover_the_days_grace_cannot() {
+        let mut goal = goal_with_streak(30, date(10));
+        goal.freeze_tokens = 2;
+
+        // Three missed days: grace covers two, a token covers the most recent one
+        StreakManager::update_goal_streak(&mut goal, date(14), &StreakPolicy::default(), every_day);
+
+        assert_eq!(goal.current_streak, 31);
+        assert_eq!(goal.freeze_tokens, 1);
+        assert_eq!(goal.grace_days, vec![date(11), date(12)]);
+        assert!(goal.is_frozen(date(13)));
+    }
+
+    #[test]
+    fn freeze_tokens_protect_streaks_too_short_for_grace() {
+        let mut goal = goal_with_streak(5, date(10));
+        goal.freeze_tokens = 1;
+
+        StreakManager::update_goal_streak(&mut goal, date(12), &StreakPolicy::default(), every_day);
+
+        assert_eq!(goal.current_streak, 6);
+        assert_eq!(goal.freeze_tokens, 0);
+        assert!(goal.is_frozen(date(11)));
+    }
+
+    #[test]
+    fn tokens_are_not_spent_when_they_cannot_save_the_streak() {
+        let mut goal = goal_with_streak(5, date(10));
+        goal.freeze_tokens = 1;
+
+        StreakManager::update_goal_streak(&mut goal, date(13), &StreakPolicy::default(), every_day);
+
+        assert_eq!(goal.current_streak, 1);
+        assert_eq!(goal.freeze_tokens, 1);
+        assert!(goal.freeze_history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn daily_maintenance_consumes_a_freeze_instead_of_resetting() {
+        let mut goal = goal_with_streak(5, date(10));
+        goal.freeze_tokens = 2;
+        let mut data = guild_with_user(vec![goal], Vec::new());
+
+        // Missed the 11th: one token is spent and the streak survives
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(12)).await.unwrap();
+        assert_eq!(resets, 0);
+        let goal = goal_in_guild(&data, "read");
+        assert_eq!((goal.current_streak, goal.freeze_tokens), (5, 1));
+
+        // Running again the same day doesn't spend the frozen day twice
+        StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(12)).await.unwrap();
+        assert_eq!(goal_in_guild(&data, "read").freeze_tokens, 1);
+
+        // Missed the 12th and 13th too: the last token covers one, then the streak resets
+        StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(13)).await.unwrap();
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(14)).await.unwrap();
+        assert_eq!(resets, 1);
+        let goal = goal_in_guild(&data, "read");
+        assert_eq!((goal.current_streak, goal.freeze_tokens), (0, 0));
+    }
+
+    #[test]
+    fn days_excluded_as_unexpected_are_not_missed() {
+        let mut goal = goal_with_streak(5, date(10));
+        let pause = PausePeriod { from: date(11), to: date(14) };
+
+        StreakManager::update_goal_streak(&mut goal, date(15), &StreakPolicy::default(), |day| !pause.contains(day));
+
+        assert_eq!(goal.current_streak, 6);
+        assert!(goal.freeze_history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn daily_maintenance_skips_paused_users_and_resumes_afterwards() {
+        let goal = goal_with_streak(5, date(10));
+        let mut data = guild_with_user(vec![goal], vec![PausePeriod { from: date(11), to: date(14) }]);
+
+        for day in 12..=15 {
+            let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(day)).await.unwrap();
+            assert_eq!(resets, 0);
+        }
+        assert_eq!(goal_in_guild(&data, "read").current_streak, 5);
+
+        // Missing the first day after the break counts again
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(16)).await.unwrap();
+        assert_eq!(resets, 1);
+    }
+
+    #[test]
+    fn hashtags_pick_goals() {
+        let goals = vec![
+            Goal::new("exercise".to_string(), "Exercise".to_string(), post_at(1)),
+            Goal::new("reading".to_string(), "Read".to_string(), post_at(1)),
+        ];
+
+        assert_eq!(StreakManager::tagged_goals("Ran 5k #Exercise!", &goals), vec!["exercise"]);
+        assert_eq!(StreakManager::tagged_goals("#reading and #exercise", &goals), vec!["exercise", "reading"]);
+        assert!(StreakManager::tagged_goals("did some reading", &goals).is_empty());
+        assert!(StreakManager::tagged_goals("#knitting", &goals).is_empty());
+    }
+
+    #[tokio::test]
+    async fn each_goal_keeps_its_own_streak() {
+        let mut exercise = goal_with_streak(5, date(11));
+        exercise.name = "exercise".to_string();
+        let reading = goal_with_streak(8, date(10));
+        let mut data = guild_with_user(vec![exercise, reading], Vec::new());
+
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(12)).await.unwrap();
+
+        assert_eq!(resets, 1);
+        assert_eq!(goal_in_guild(&data, "exercise").current_streak, 5);
+        assert_eq!(goal_in_guild(&data, "read").current_streak, 0);
+    }
+
+    fn weekly_goal(times: u32) -> Goal {
+        let mut goal = Goal::new("gym".to_string(), "Gym".to_string(), post_at(1));
+        goal.frequency = GoalFrequency::Weekly { times };
+        goal
+    }
+
+    #[test]
+    fn weekly_streaks_grow_once_the_target_is_met() {
+        // 2025-11-03 and 2025-11-10 are Mondays
+        let mut goal = weekly_goal(3);
+        for day in [3, 5] {
+            StreakManager::update_weekly_streak(&mut goal, date(day), 3, every_day);
+        }
+        assert_eq!((goal.current_streak, goal.week_checkins), (0, 2));
+
+        StreakManager::update_weekly_streak(&mut goal, date(7), 3, every_day);
+        StreakManager::update_weekly_streak(&mut goal, date(8), 3, every_day);
+        assert_eq!((goal.current_streak, goal.week_checkins), (1, 4));
+
+        for day in [10, 12, 16] {
+            StreakManager::update_weekly_streak(&mut goal, date(day), 3, every_day);
+        }
+        assert_eq!((goal.current_streak, goal.longest_streak), (2, 2));
+        assert_eq!(goal.last_met_week, Some(date(10)));
+    }
+
+    #[tokio::test]
+    async fn weekly_goals_are_judged_when_the_week_ends() {
+        let mut goal = weekly_goal(2);
+        goal.current_streak = 4;
+        goal.last_met_week = Some(date(3));
+        goal.last_checkin_date = Some(date(11));
+        goal.week_checkins = 1;
+        let mut data = guild_with_user(vec![goal], Vec::new());
+
+        // Mid-week nothing is decided yet
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(14)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "gym").current_streak), (0, 4));
+
+        // The week of the 10th ended one check-in short
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(17)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "gym").current_streak), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn weeks_with_fewer_expected_days_need_fewer_checkins() {
+        let mut goal = weekly_goal(3);
+        goal.current_streak = 4;
+        goal.last_met_week = Some(date(3));
+        goal.last_checkin_date = Some(date(10));
+        goal.week_checkins = 1;
+        // Away from Tuesday the 11th to the end of the week: Monday's check-in is enough
+        let mut data = guild_with_user(vec![goal], vec![PausePeriod { from: date(11), to: date(16) }]);
+
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(17)).await.unwrap();
+        let goal = goal_in_guild(&data, "gym");
+        assert_eq!((resets, goal.current_streak, goal.last_met_week), (0, 5, Some(date(10))));
+
+        // A week spent entirely on a break doesn't count either way
+        let mut goal = weekly_goal(3);
+        goal.current_streak = 4;
+        goal.last_met_week = Some(date(3));
+        let mut data = guild_with_user(vec![goal], vec![PausePeriod { from: date(10), to: date(16) }]);
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(17)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "gym").current_streak), (0, 4));
+    }
+
+    #[tokio::test]
+    async fn rest_days_never_break_a_streak() {
+        // 2025-11-08 and 2025-11-09 are a weekend
+        let goal = goal_with_streak(5, date(7));
+        let mut data = guild_with_user(vec![goal], Vec::new());
+        data.get_user_mut("1000", "2000").unwrap().rest_days.weekdays = vec![Weekday::Sat, Weekday::Sun];
+
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(10)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "read").current_streak), (0, 5));
+
+        // Single rest dates work the same way
+        let mut goal = goal_with_streak(5, date(10));
+        let rest_days = RestDays { weekdays: Vec::new(), dates: vec![date(11)] };
+        StreakManager::update_goal_streak(&mut goal, date(12), &StreakPolicy::default(), |day| !rest_days.contains(day));
+        assert_eq!(goal.current_streak, 6);
+    }
+
+    #[tokio::test]
+    async fn direct_checkins_need_an_open_post_and_count_once() {
+        let data = guild_with_user(vec![Goal::new("read".to_string(), "Read".to_string(), post_at(1))], Vec::new());
+        let (manager, clock) = manager_at(data, post_at(12) + Duration::hours(1));
+
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::NoOpenCheckin));
+
+        manager.data.write().await.set_daily_post(daily_post(post_at(12)));
+
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::Recorded { goals, .. } if goals[0].current_streak == 1));
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::AlreadyCheckedIn));
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2001), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::NotRegistered));
+
+        // The window closes 24 hours after the post
+        clock.advance(Duration::days(1));
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::NoOpenCheckin));
+
+        let data = manager.data.read().await;
+        assert_eq!(data.get_user_checkins("1000", "2000").len(), 1);
+    }
+
+    #[test]
+    fn requirements_explain_every_shortfall() {
+        let requirements = CheckinRequirements {
+            min_length: 20,
+            require_attachment: true,
+            keyword: Some("#Done".to_string()),
+        };
+
+        assert_eq!(requirements.problems("ran 5k #done", 0).len(), 2);
+        assert!(requirements.problems("Ran 5k before work today #DONE", 1).is_empty());
+        assert!(CheckinRequirements::default().problems("", 0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejected_checkins_leave_streaks_alone() {
+        let mut data = guild_with_user(vec![goal_with_streak(5, date(11))], Vec::new());
+        let mut config = ServerConfig::new("1000".to_string(), post_at(1));
+        config.checkin_requirements.min_length = 10;
+        data.add_or_update_server(config);
+        data.set_daily_post(daily_post(post_at(12)));
+        let (manager, _) = manager_at(data, post_at(12));
+
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "ran", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::Rejected(problems) if problems.len() == 1));
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").current_streak, 5);
+
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "ran 5k today", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::Recorded { .. }));
+    }
+
+    /// A guild whose user checked in on `read` on each of `days`, one message per day
+    fn guild_with_checkins(days: &[u32]) -> BotData {
+        let mut data = guild_with_user(vec![Goal::new("read".to_string(), "Read".to_string(), post_at(1))], Vec::new());
+        for day in days {
+            let goal = data.get_user_mut("1000", "2000").unwrap().goal_mut("read").unwrap();
+            let before = goal.snapshot();
+            StreakManager::update_goal_streak(goal, date(*day), &StreakPolicy::default(), every_day);
+            data.add_checkin("1000".to_string(), CheckinRecord {
+                user_id: "2000".to_string(),
+                goal: Some("read".to_string()),
+                checkin_date: date(*day),
+                message_id: Some(day.to_string()),
+                thread_id: None,
+                daily_post_id: None,
+                created_at: post_at(*day),
+                before: Some(before),
+            });
+        }
+        data
+    }
+
+    #[test]
+    fn revoking_a_checkin_replays_the_later_ones() {
+        let mut data = guild_with_checkins(&[10, 11, 12]);
+        let revoked = StreakManager::revoke_checkins(&mut data, "1000", "12");
+        assert_eq!(revoked.len(), 1);
+        let goal = goal_in_guild(&data, "read");
+        assert_eq!((goal.current_streak, goal.last_checkin_date), (2, Some(date(11))));
+
+        // Without the 11th, the 12th starts a new streak
+        let mut data = guild_with_checkins(&[10, 11, 12]);
+        StreakManager::revoke_checkins(&mut data, "1000", "11");
+        let goal = goal_in_guild(&data, "read");
+        assert_eq!((goal.current_streak, goal.longest_streak), (1, 1));
+        assert_eq!(data.get_checkins("1000").len(), 2);
+        assert_eq!(data.get_checkins("1000")[1].before.as_ref().unwrap().last_checkin_date, Some(date(10)));
+
+        assert!(StreakManager::revoke_checkins(&mut data, "1000", "11").is_empty());
+    }
+
+    #[tokio::test]
+    async fn servers_can_keep_deleted_checkins() {
+        let mut data = guild_with_checkins(&[10]);
+        let mut config = ServerConfig::new("1000".to_string(), post_at(1));
+        config.keep_deleted_checkins = true;
+        data.add_or_update_server(config);
+        let (manager, _) = manager_at(data, post_at(10));
+
+        manager.process_message_delete(GuildId::new(1000), MessageId::new(10)).await.unwrap();
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").current_streak, 1);
+
+        // By default the deletion takes the check-in back
+        manager.data.write().await.add_or_update_server(ServerConfig::new("1000".to_string(), post_at(1)));
+        manager.process_message_delete(GuildId::new(1000), MessageId::new(10)).await.unwrap();
+        let data = manager.data.read().await;
+        assert_eq!(goal_in_guild(&data, "read").current_streak, 0);
+        assert!(data.get_checkins("1000").is_empty());
+    }
+
+    #[tokio::test]
+    async fn checkins_report_the_milestones_they_reach() {
+        let mut data = guild_with_user(vec![goal_with_streak(6, date(11)), goal_with_streak(2, date(11))], Vec::new());
+        data.get_user_mut("1000", "2000").unwrap().goals[1].name = "write".to_string();
+        data.set_daily_post(daily_post(post_at(12)));
+        let (manager, _) = manager_at(data, post_at(12));
+
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::Recorded { milestones, .. } if milestones == vec!["read"]));
+        assert!(StreakManager::is_streak_milestone(300));
+        assert!(!StreakManager::is_streak_milestone(0));
+    }
+
+    #[tokio::test]
+    async fn checkins_count_for_the_day_in_the_members_timezone() {
+        let mut data = guild_with_user(vec![Goal::new("read".to_string(), "Read".to_string(), post_at(1))], Vec::new());
+        data.get_user_mut("1000", "2000").unwrap().timezone = Some("Asia/Tokyo".to_string());
+        data.set_daily_post(daily_post(post_at(18)));
+
+        // 23:00 UTC on the 18th is 08:00 on the 19th in Tokyo
+        let (manager, _) = manager_at(data, post_at(18) + Duration::hours(14));
+        let outcome = manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap();
+        assert!(matches!(outcome, CheckinOutcome::Recorded { .. }));
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").last_checkin_date, Some(date(19)));
+    }
+
+    #[tokio::test]
+    async fn maintenance_judges_each_member_in_their_timezone() {
+        let mut data = guild_with_user(vec![goal_with_streak(5, date(18))], Vec::new());
+        let mut los_angeles = UserData::new("2001".to_string(), post_at(1));
+        los_angeles.goals = vec![goal_with_streak(5, date(18))];
+        los_angeles.timezone = Some("America/Los_Angeles".to_string());
+        data.add_or_update_user("1000".to_string(), los_angeles);
+
+        // 06:00 UTC on the 20th: the 19th is over in UTC but not yet in Los Angeles
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", post_at(20) - Duration::hours(3)).await.unwrap();
+        assert_eq!(resets, 1);
+        assert_eq!(goal_in_guild(&data, "read").current_streak, 0);
+        assert_eq!(data.get_user("1000", "2001").unwrap().goal("read").unwrap().current_streak, 5);
+    }
+
+    #[tokio::test]
+    async fn maintenance_uses_the_server_day_across_daylight_saving_changes() {
+        let spring_forward = |hour, minute| {
+            NaiveDate::from_ymd_opt(2025, 3, 9).unwrap().and_time(chrono::NaiveTime::MIN).and_utc()
+                + Duration::hours(hour)
+                + Duration::minutes(minute)
+        };
+        let mut data = guild_with_user(vec![goal_with_streak(5, NaiveDate::from_ymd_opt(2025, 3, 8).unwrap())], Vec::new());
+        let mut config = ServerConfig::new("1000".to_string(), post_at(1));
+        config.timezone = "America/New_York".to_string();
+        config.daily_time = "02:30".to_string();
+        data.add_or_update_server(config);
+
+        // The 02:30 post falls in the gap and goes out at 03:00 EDT (07:00 UTC) on the 9th
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", spring_forward(7, 0)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "read").current_streak), (0, 5));
+
+        // 01:00 UTC on the 10th is still the evening of the 9th in New York
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", spring_forward(25, 0)).await.unwrap();
+        assert_eq!(resets, 0);
+
+        let resets = StreakManager::reset_streaks_for_guild(&mut data, "1000", spring_forward(30, 30)).await.unwrap();
+        assert_eq!((resets, goal_in_guild(&data, "read").current_streak), (1, 0));
+    }
+
+    fn on(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    /// One day of the scheduler's cycle on the fake clock: maintenance and the 09:00 UTC post,
+    /// then the member checks in at noon if `checks_in`
+    async fn simulate_day(manager: &StreakManager, clock: &FakeClock, day: NaiveDate, checks_in: bool) -> Option<CheckinOutcome> {
+        clock.set(day.and_hms_opt(9, 0, 0).unwrap().and_utc());
+        {
+            let mut data = manager.data.write().await;
+            let now = data.now();
+            StreakManager::reset_streaks_for_guild(&mut data, "1000", now).await.unwrap();
+            data.set_daily_post(daily_post(now));
+        }
+        if !checks_in {
+            return None;
+        }
+        clock.advance(Duration::hours(3));
+        Some(manager.checkin_directly(GuildId::new(1000), UserId::new(2000), "", 0).await.unwrap())
+    }
+
+    /// Simulate each day from `from` through `to`, expecting every check-in made to count
+    async fn simulate_days(manager: &StreakManager, clock: &FakeClock, from: NaiveDate, to: NaiveDate, checks_in: impl Fn(NaiveDate) -> bool) {
+        for day in from.iter_days().take_while(|day| *day <= to) {
+            if let Some(outcome) = simulate_day(manager, clock, day, checks_in(day)).await {
+                assert!(matches!(outcome, CheckinOutcome::Recorded { .. }), "check-in on {} didn't count: {:?}", day, outcome);
             }
-        } else {
-            false
         }
     }
+
+    #[tokio::test]
+    async fn months_of_checkins_with_misses_and_grace_periods() {
+        let mut data = guild_with_user(vec![Goal::new("read".to_string(), "Read".to_string(), post_at(1))], Vec::new());
+        let mut config = ServerConfig::new("1000".to_string(), post_at(1));
+        config.streak_policy.freeze_every_days = 0;
+        data.add_or_update_server(config);
+        let (manager, clock) = manager_at(data, on(9, 1).and_hms_opt(0, 0, 0).unwrap().and_utc());
+
+        simulate_days(&manager, &clock, on(9, 1), on(10, 10), |_| true).await;
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").current_streak, 40);
+
+        // Two days off are excused, but don't add to the streak
+        simulate_days(&manager, &clock, on(10, 11), on(10, 20), |day| day > on(10, 12)).await;
+        {
+            let data = manager.data.read().await;
+            let goal = goal_in_guild(&data, "read");
+            assert_eq!((goal.current_streak, goal.longest_streak), (48, 48));
+            assert_eq!(goal.grace_days, vec![on(10, 11), on(10, 12)]);
+        }
+
+        // A third miss within 30 days is one too many: maintenance resets the streak
+        simulate_day(&manager, &clock, on(10, 21), false).await;
+        simulate_day(&manager, &clock, on(10, 22), false).await;
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").current_streak, 0);
+        simulate_days(&manager, &clock, on(10, 23), on(11, 20), |_| true).await;
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "read").current_streak, 29);
+
+        // Grace is back once the new streak is long enough
+        simulate_days(&manager, &clock, on(11, 21), on(11, 30), |day| day != on(11, 22)).await;
+        let data = manager.data.read().await;
+        let goal = goal_in_guild(&data, "read");
+        assert_eq!((goal.current_streak, goal.longest_streak), (38, 48));
+        assert_eq!(goal.grace_days, vec![on(11, 22)]);
+        assert_eq!(data.get_user_checkins("1000", "2000").len(), 86);
+    }
+
+    #[tokio::test]
+    async fn re_registering_after_time_away_starts_a_new_streak() {
+        let data = guild_with_user(vec![Goal::new("read".to_string(), "Read".to_string(), post_at(1))], Vec::new());
+        let (manager, clock) = manager_at(data, on(9, 1).and_hms_opt(0, 0, 0).unwrap().and_utc());
+        simulate_days(&manager, &clock, on(9, 1), on(9, 20), |_| true).await;
+
+        // Deregistered for three weeks: check-ins don't count, and nothing is held against the streak
+        {
+            let mut data = manager.data.write().await;
+            let user = data.get_user_mut("1000", "2000").unwrap();
+            user.goal_mut("read").unwrap().is_active = false;
+            user.is_active = false;
+        }
+        for day in on(9, 21).iter_days().take_while(|day| *day <= on(10, 11)) {
+            let outcome = simulate_day(&manager, &clock, day, true).await;
+            assert!(matches!(outcome, Some(CheckinOutcome::NotRegistered)));
+        }
+        {
+            let data = manager.data.read().await;
+            let goal = goal_in_guild(&data, "read");
+            assert_eq!((goal.current_streak, goal.freeze_tokens), (20, 2));
+            assert!(goal.freeze_history.iter().all(|event| event.kind == FreezeEventKind::Earned));
+        }
+
+        // Coming back restarts the streak without the gap counting as missed days
+        {
+            let mut data = manager.data.write().await;
+            let now = data.now();
+            let user = data.get_user_mut("1000", "2000").unwrap();
+            user.goal_mut("read").unwrap().reactivate("Read".to_string(), GoalFrequency::Daily, now);
+            user.is_active = true;
+        }
+        simulate_days(&manager, &clock, on(10, 12), on(10, 25), |_| true).await;
+        let goal = goal_in_guild(&*manager.data.read().await, "read").clone();
+        assert_eq!((goal.current_streak, goal.longest_streak, goal.freeze_tokens), (14, 20, 2));
+    }
+
+    #[tokio::test]
+    async fn weekly_goals_over_two_months() {
+        // 2025-09-01 is a Monday; the member checks in Monday, Wednesday and Friday
+        let data = guild_with_user(vec![weekly_goal(3)], Vec::new());
+        let (manager, clock) = manager_at(data, on(9, 1).and_hms_opt(0, 0, 0).unwrap().and_utc());
+        let three_times_a_week = |day: NaiveDate| matches!(day.weekday(), Weekday::Mon | Weekday::Wed | Weekday::Fri);
+
+        simulate_days(&manager, &clock, on(9, 1), on(9, 28), three_times_a_week).await;
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "gym").current_streak, 4);
+
+        // Skipping Friday leaves the week a check-in short, which Monday's maintenance notices
+        simulate_days(&manager, &clock, on(9, 29), on(10, 6), |day| three_times_a_week(day) && day != on(10, 3)).await;
+        assert_eq!(goal_in_guild(&*manager.data.read().await, "gym").current_streak, 0);
+
+        simulate_days(&manager, &clock, on(10, 7), on(11, 9), three_times_a_week).await;
+        let data = manager.data.read().await;
+        let goal = goal_in_guild(&data, "gym");
+        assert_eq!((goal.current_streak, goal.longest_streak, goal.last_met_week), (5, 5, Some(on(11, 3))));
+    }
 }
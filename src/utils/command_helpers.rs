@@ -1,13 +1,23 @@
+// The helpers return serenity::Result because that is the error type commands propagate.
+// serenity::Error is large, so each of them allows clippy::result_large_err.
+
 use serenity::{
     model::{
         application::{CommandDataOptionValue, CommandInteraction},
-        id::ChannelId,
-        permissions::Permissions,
+        channel::ReactionType,
+        id::{ChannelId, RoleId},
     },
     prelude::*,
 };
-use chrono::NaiveTime;
+use chrono::{NaiveDate, NaiveTime, Weekday};
 use chrono_tz::Tz;
+use crate::{
+    bot::SharedBotData,
+    data::MAX_GOAL_NAME_LENGTH,
+    permissions::{self, Access, Invoker},
+    platform::DiscordPlatform,
+    schedule::PostSchedule,
+};
 
 /// Extracts the guild ID from a Discord command interaction.
 /// 
@@ -22,6 +32,7 @@ use chrono_tz::Tz;
 /// ```rust
 /// let guild_id = get_guild_id(command)?;
 /// ```
+#[allow(clippy::result_large_err)]
 pub fn get_guild_id(command: &CommandInteraction) -> serenity::Result<String> {
     command
         .guild_id
@@ -61,6 +72,7 @@ pub fn get_user_id(command: &CommandInteraction) -> String {
 /// ```rust
 /// let goal = get_string_option(command, "goal")?;
 /// ```
+#[allow(clippy::result_large_err)]
 pub fn get_string_option(command: &CommandInteraction, name: &str) -> serenity::Result<String> {
     let option = command
         .data
@@ -96,6 +108,7 @@ pub fn get_string_option(command: &CommandInteraction, name: &str) -> serenity::
 /// ```rust
 /// let channel_id = get_channel_option(command, "channel")?;
 /// ```
+#[allow(clippy::result_large_err)]
 pub fn get_channel_option(command: &CommandInteraction, name: &str) -> serenity::Result<ChannelId> {
     let option = command
         .data
@@ -110,46 +123,125 @@ pub fn get_channel_option(command: &CommandInteraction, name: &str) -> serenity:
     }
 }
 
-/// Checks if a user has administrator permissions in the guild.
+/// Extracts an integer option value from a Discord command interaction.
 /// 
 /// # Arguments
-/// * `ctx` - The Discord context
 /// * `command` - The Discord command interaction
+/// * `name` - The name of the integer option to extract
 /// 
 /// # Returns
-/// * `Ok(bool)` - Whether the user has admin permissions
-/// * `Err(serenity::Error)` - If permission check fails
+/// * `Ok(i64)` - The integer value
+/// * `Err(serenity::Error)` - If the option is missing or not an integer
 /// 
 /// # Example
 /// ```rust
-/// if !is_admin(ctx, command).await? {
-///     return Ok(error_response("This command requires administrator permissions."));
-/// }
+/// let min_streak = get_integer_option(command, "min-streak")?;
 /// ```
-pub async fn is_admin(ctx: &Context, command: &CommandInteraction) -> serenity::Result<bool> {
-    let guild_id = command
-        .guild_id
-        .ok_or_else(|| serenity::Error::Other("This command can only be used in a server"))?;
+#[allow(clippy::result_large_err)]
+pub fn get_integer_option(command: &CommandInteraction, name: &str) -> serenity::Result<i64> {
+    let option = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .ok_or_else(|| serenity::Error::Other("Missing required integer argument"))?;
     
-    // Get the guild and member info from HTTP API (not cache-dependent)
-    let guild = ctx.http.get_guild(guild_id).await?;
-    let member = guild_id.member(&ctx.http, command.user.id).await?;
+    match &option.value {
+        CommandDataOptionValue::Integer(value) => Ok(*value),
+        _ => Err(serenity::Error::Other("Argument is not an integer")),
+    }
+}
+
+/// Extracts a boolean option value from a Discord command interaction.
+/// 
+/// # Arguments
+/// * `command` - The Discord command interaction
+/// * `name` - The name of the boolean option to extract
+/// 
+/// # Returns
+/// * `Ok(bool)` - The boolean value
+/// * `Err(serenity::Error)` - If the option is missing or not a boolean
+/// 
+/// # Example
+/// ```rust
+/// let counts = get_boolean_option(command, "grace-counts")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn get_boolean_option(command: &CommandInteraction, name: &str) -> serenity::Result<bool> {
+    let option = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .ok_or_else(|| serenity::Error::Other("Missing required boolean argument"))?;
     
-    // Check if user is the guild owner (owners always have admin)
-    if guild.owner_id == command.user.id {
-        return Ok(true);
+    match &option.value {
+        CommandDataOptionValue::Boolean(value) => Ok(*value),
+        _ => Err(serenity::Error::Other("Argument is not a boolean")),
     }
+}
+
+/// Extracts a role option value from a Discord command interaction.
+/// 
+/// # Arguments
+/// * `command` - The Discord command interaction
+/// * `name` - The name of the role option to extract
+/// 
+/// # Returns
+/// * `Ok(RoleId)` - The role ID
+/// * `Err(serenity::Error)` - If the option is missing or not a role
+/// 
+/// # Example
+/// ```rust
+/// let role_id = get_role_option(command, "add")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn get_role_option(command: &CommandInteraction, name: &str) -> serenity::Result<RoleId> {
+    let option = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .ok_or_else(|| serenity::Error::Other("Missing required role argument"))?;
     
-    // Check if any of the user's roles have administrator permission
-    for role_id in &member.roles {
-        if let Some(role) = guild.roles.get(role_id) {
-            if role.permissions.contains(Permissions::ADMINISTRATOR) {
-                return Ok(true);
-            }
-        }
+    match &option.value {
+        CommandDataOptionValue::Role(id) => Ok(*id),
+        _ => Err(serenity::Error::Other("Argument is not a role")),
     }
+}
+
+/// Works out how much of the server's configuration the invoking user may change.
+/// 
+/// Administrators and the server owner get `Access::Admin`, members holding one of the
+/// server's manager roles get `Access::Manager`.
+/// 
+/// # Arguments
+/// * `ctx` - The Discord context
+/// * `command` - The Discord command interaction
+/// * `data` - The bot data holding the server's manager roles
+/// 
+/// # Returns
+/// * `Ok(Access)` - The user's access level
+/// * `Err(serenity::Error)` - If the command was not executed in a server or the lookup fails
+/// 
+/// # Example
+/// ```rust
+/// if access(ctx, command, &data).await? < Access::Manager {
+///     return Ok(error_response("This command is for administrators and managers."));
+/// }
+/// ```
+pub async fn access(ctx: &Context, command: &CommandInteraction, data: &SharedBotData) -> serenity::Result<Access> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| serenity::Error::Other("This command can only be used in a server"))?;
+    let config = data.read().await.get_server_config(&guild_id.to_string()).cloned();
     
-    Ok(false)
+    permissions::access(&DiscordPlatform::new(ctx), config.as_ref(), guild_id, &Invoker::from(command))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check permissions of user {} in guild {}: {}", command.user.id, guild_id, e);
+            serenity::Error::Other("Failed to check your permissions")
+        })
 }
 
 /// Validates and parses a timezone string.
@@ -165,6 +257,7 @@ pub async fn is_admin(ctx: &Context, command: &CommandInteraction) -> serenity::
 /// ```rust
 /// let tz = validate_timezone("America/New_York")?;
 /// ```
+#[allow(clippy::result_large_err)]
 pub fn validate_timezone(timezone_str: &str) -> serenity::Result<String> {
     // Try to parse the timezone
     timezone_str.parse::<Tz>()
@@ -173,6 +266,117 @@ pub fn validate_timezone(timezone_str: &str) -> serenity::Result<String> {
     Ok(timezone_str.to_string())
 }
 
+/// Validates and parses a date string in YYYY-MM-DD format.
+/// 
+/// # Arguments
+/// * `date_str` - The date string to validate (e.g., "2025-12-24")
+/// 
+/// # Returns
+/// * `Ok(NaiveDate)` - The parsed date
+/// * `Err(serenity::Error)` - If the date format is invalid
+/// 
+/// # Example
+/// ```rust
+/// let from = validate_date("2025-12-24")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn validate_date(date_str: &str) -> serenity::Result<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|_| serenity::Error::Other("Invalid date format. Use YYYY-MM-DD format (e.g., '2025-12-24')"))
+}
+
+/// Parses a list of weekdays such as "sat, sun" or "Saturday Sunday".
+/// 
+/// "none" gives an empty list.
+/// 
+/// # Arguments
+/// * `text` - Weekday names or abbreviations, separated by commas or spaces
+/// 
+/// # Returns
+/// * `Ok(Vec<Weekday>)` - The weekdays, Monday first and without duplicates
+/// * `Err(serenity::Error)` - If a name isn't a weekday
+/// 
+/// # Example
+/// ```rust
+/// let weekdays = parse_weekdays("sat sun")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn parse_weekdays(text: &str) -> serenity::Result<Vec<Weekday>> {
+    if text.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+    let mut weekdays = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.parse::<Weekday>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| serenity::Error::Other("Invalid weekdays. Use names like 'sat sun' or 'Saturday, Sunday', or 'none'"))?;
+    weekdays.sort_by_key(Weekday::num_days_from_monday);
+    weekdays.dedup();
+
+    Ok(weekdays)
+}
+
+/// Validates a goal name used in goal selectors and `#name` check-in tags.
+/// 
+/// Names are lowercased and may contain letters, digits and dashes.
+/// 
+/// # Arguments
+/// * `name` - The goal name to validate (e.g., "exercise", "spanish-2")
+/// 
+/// # Returns
+/// * `Ok(String)` - The validated, lowercased name
+/// * `Err(serenity::Error)` - If the name is too long or has other characters
+/// 
+/// # Example
+/// ```rust
+/// let name = validate_goal_name("Reading")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn validate_goal_name(name: &str) -> serenity::Result<String> {
+    let name = name.trim().to_lowercase();
+    let valid_chars = name.chars().all(|c| c.is_alphanumeric() || c == '-');
+    if name.is_empty() || name.chars().count() > MAX_GOAL_NAME_LENGTH || !valid_chars {
+        return Err(serenity::Error::Other("Invalid goal name. Use up to 20 letters, digits or dashes (e.g., 'exercise', 'spanish-2')"));
+    }
+
+    Ok(name)
+}
+
+/// Validates an emoji for the bot to react with.
+/// 
+/// Accepts a single Unicode emoji or a custom server emoji (`<:name:id>`); "none" gives `None`.
+/// 
+/// # Arguments
+/// * `text` - The emoji to validate (e.g., "✅", "<:done:123456789012345678>")
+/// 
+/// # Returns
+/// * `Ok(Option<String>)` - The emoji, or `None` for "none"
+/// * `Err(serenity::Error)` - If it isn't an emoji
+/// 
+/// # Example
+/// ```rust
+/// let emoji = validate_emoji("🔥")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn validate_emoji(text: &str) -> serenity::Result<Option<String>> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    let valid = match ReactionType::try_from(text) {
+        Ok(ReactionType::Custom { .. }) => true,
+        // Unicode emoji are short and never contain letters, digits or spaces
+        Ok(_) => text.chars().count() <= 8 && !text.chars().any(|c| c.is_ascii_alphanumeric() || c.is_whitespace()),
+        Err(_) => false,
+    };
+    if !valid {
+        return Err(serenity::Error::Other("Invalid emoji. Use a single emoji like '✅' or one of this server's custom emoji, or 'none'"));
+    }
+
+    Ok(Some(text.to_string()))
+}
+
 /// Validates and parses a time string in HH:MM format.
 /// 
 /// # Arguments
@@ -186,6 +390,7 @@ pub fn validate_timezone(timezone_str: &str) -> serenity::Result<String> {
 /// ```rust
 /// let time = validate_time_format("09:30")?;
 /// ```
+#[allow(clippy::result_large_err)]
 pub fn validate_time_format(time_str: &str) -> serenity::Result<String> {
     // Try to parse the time in HH:MM format
     NaiveTime::parse_from_str(time_str, "%H:%M")
@@ -193,3 +398,27 @@ pub fn validate_time_format(time_str: &str) -> serenity::Result<String> {
     
     Ok(time_str.to_string())
 }
+
+/// Validates a cron expression for the daily check-in schedule.
+/// 
+/// Accepts the standard five-field form (minute hour day-of-month month day-of-week)
+/// as well as the six/seven-field form with seconds (and years).
+/// 
+/// # Arguments
+/// * `expression` - The cron expression to validate (e.g., "0 9 * * Mon-Fri")
+/// 
+/// # Returns
+/// * `Ok(String)` - The validated, trimmed expression
+/// * `Err(serenity::Error)` - If the expression is invalid or never fires
+/// 
+/// # Example
+/// ```rust
+/// let schedule = validate_cron_expression("0 9,18 * * *")?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn validate_cron_expression(expression: &str) -> serenity::Result<String> {
+    PostSchedule::parse_cron(expression)
+        .map_err(|_| serenity::Error::Other("Invalid cron expression. Use five fields: minute hour day-of-month month day-of-week (e.g., '0 9 * * Mon-Fri')"))?;
+
+    Ok(expression.trim().to_string())
+}
@@ -1,7 +1,7 @@
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage, CreateEmbed};
 
 pub fn default_response(message: &str) -> CreateInteractionResponse {
-    let data = CreateInteractionResponseMessage::new().content(format!("{}", message));
+    let data = CreateInteractionResponseMessage::new().content(message);
     CreateInteractionResponse::Message(data)
 }
 
@@ -9,3 +9,9 @@ pub fn embed_response(embed: CreateEmbed) -> CreateInteractionResponse {
     let data = CreateInteractionResponseMessage::new().add_embed(embed);
     CreateInteractionResponse::Message(data)
 }
+
+/// A reply only the user who triggered the interaction can see
+pub fn ephemeral_response(message: &str) -> CreateInteractionResponse {
+    let data = CreateInteractionResponseMessage::new().content(message).ephemeral(true);
+    CreateInteractionResponse::Message(data)
+}